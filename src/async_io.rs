@@ -0,0 +1,185 @@
+use crate::{
+    read::{decode, header_len},
+    ReadError, ReadGraph, WriteGraph,
+};
+use std::io;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Asynchronously reads graph6/digraph6 formatted graphs line by line
+///
+/// This is the async equivalent of [`GraphReader`](crate::GraphReader).
+///
+/// # Example
+/// ```
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// use graph6_rs::{AsyncGraphReader, Graph};
+/// let mut reader = AsyncGraphReader::new("A_\nBw\n".as_bytes());
+/// let graph: Graph = reader.next_graph().await.unwrap().unwrap();
+/// assert_eq!(graph.n, 2);
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct AsyncGraphReader<R> {
+    inner: R,
+    buf: Vec<u8>,
+    line: usize,
+}
+impl<R: AsyncBufRead + Unpin> AsyncGraphReader<R> {
+    /// Creates a new reader over a buffered async input
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            line: 0,
+        }
+    }
+
+    /// Returns the (1-based) line number of the most recently read line
+    pub fn line_number(&self) -> usize {
+        self.line
+    }
+
+    /// Reads the next encoded graph without decoding it
+    ///
+    /// Returns `Ok(None)` once the input is exhausted. A line which is not
+    /// valid UTF-8 is returned as an [`io::ErrorKind::InvalidData`] error,
+    /// after which reading continues with the following line.
+    pub async fn next_line(&mut self) -> io::Result<Option<&str>> {
+        match self.next_bytes().await? {
+            Some(bytes) => std::str::from_utf8(bytes)
+                .map(Some)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)),
+            None => Ok(None),
+        }
+    }
+
+    /// Reads the next non-empty line as bytes, without line ending or header
+    async fn next_bytes(&mut self) -> io::Result<Option<&[u8]>> {
+        loop {
+            self.buf.clear();
+            if self.inner.read_until(b'\n', &mut self.buf).await? == 0 {
+                return Ok(None);
+            }
+            self.line += 1;
+            let mut end = self.buf.len();
+            while end > 0 && matches!(self.buf[end - 1], b'\n' | b'\r') {
+                end -= 1;
+            }
+            let start = if self.line == 1 {
+                header_len(&self.buf[..end])
+            } else {
+                0
+            };
+            if start < end {
+                return Ok(Some(&self.buf[start..end]));
+            }
+        }
+    }
+
+    /// Reads and decodes the next graph
+    ///
+    /// Returns `None` once the input is exhausted.
+    pub async fn next_graph<G: ReadGraph>(&mut self) -> Option<Result<G, ReadError>> {
+        let decoded = match self.next_bytes().await {
+            Ok(Some(bytes)) => decode::<G>(bytes),
+            Ok(None) => return None,
+            Err(err) => return Some(Err(ReadError::Io(err))),
+        };
+        Some(decoded.map_err(|(error, _)| ReadError::Parse {
+            line: self.line,
+            error,
+        }))
+    }
+
+    /// Consumes the reader and returns the underlying input
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+/// Asynchronously writes graphs as graph6/digraph6 formatted lines
+///
+/// This is the async equivalent of [`GraphWriter`](crate::GraphWriter).
+#[derive(Debug)]
+pub struct AsyncGraphWriter<W> {
+    inner: W,
+}
+impl<W: AsyncWrite + Unpin> AsyncGraphWriter<W> {
+    /// Creates a new writer over an async output
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Encodes a graph and writes it as a single line
+    pub async fn write_graph<G: WriteGraph + ?Sized>(&mut self, graph: &G) -> io::Result<()> {
+        self.write_line(&graph.write_graph()).await
+    }
+
+    /// Writes an already encoded graph as a single line
+    pub async fn write_line(&mut self, repr: &str) -> io::Result<()> {
+        self.inner.write_all(repr.as_bytes()).await?;
+        self.inner.write_all(b"\n").await
+    }
+
+    /// Flushes the underlying output
+    pub async fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush().await
+    }
+
+    /// Flushes and shuts down the underlying output
+    pub async fn shutdown(&mut self) -> io::Result<()> {
+        self.inner.shutdown().await
+    }
+
+    /// Consumes the writer and returns the underlying output
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::{AsyncGraphReader, AsyncGraphWriter};
+    use crate::{DiGraph, Graph, IOError, ReadError};
+
+    #[tokio::test]
+    async fn test_next_line() {
+        let mut reader = AsyncGraphReader::new(">>graph6<<A_\n\nBw\r\n".as_bytes());
+        assert_eq!(reader.next_line().await.unwrap(), Some("A_"));
+        assert_eq!(reader.next_line().await.unwrap(), Some("Bw"));
+        assert_eq!(reader.line_number(), 3);
+        assert_eq!(reader.next_line().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_next_graph() {
+        let mut reader = AsyncGraphReader::new("&AG\n&B\\o\n".as_bytes());
+        let graph: DiGraph = reader.next_graph().await.unwrap().unwrap();
+        assert_eq!(graph.bit_vec, &[0, 0, 1, 0]);
+        let graph: DiGraph = reader.next_graph().await.unwrap().unwrap();
+        assert_eq!(graph.n, 3);
+        assert!(reader.next_graph::<DiGraph>().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_next_graph_error() {
+        let mut reader = AsyncGraphReader::new("A1\n".as_bytes());
+        match reader.next_graph::<Graph>().await.unwrap() {
+            Err(ReadError::Parse { line, error }) => {
+                assert_eq!(line, 1);
+                assert_eq!(error, IOError::NonCanonicalEncoding);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_writer() {
+        let mut writer = AsyncGraphWriter::new(Vec::new());
+        let graph = Graph::from_g6("A_").unwrap();
+        writer.write_graph(&graph).await.unwrap();
+        writer.write_line("Bw").await.unwrap();
+        writer.flush().await.unwrap();
+        assert_eq!(writer.into_inner(), b"A_\nBw\n");
+    }
+}
@@ -0,0 +1,285 @@
+use std::{
+    ffi::OsStr,
+    fmt,
+    io::{self, BufRead, Write},
+    path::Path,
+};
+
+/// Magic bytes at the start of a gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Magic bytes at the start of a zstd frame
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Compression formats supported for graph files
+///
+/// Gzip and zstd streams are only decoded when the `gzip` and `zstd`
+/// features are enabled respectively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+impl Compression {
+    /// Detects the compression of a stream from its leading bytes
+    ///
+    /// # Example
+    /// ```
+    /// use graph6_rs::Compression;
+    /// assert_eq!(Compression::from_magic(&[0x1f, 0x8b, 0x08]), Compression::Gzip);
+    /// assert_eq!(Compression::from_magic(b"A_"), Compression::None);
+    /// ```
+    pub fn from_magic(bytes: &[u8]) -> Self {
+        if bytes.starts_with(&GZIP_MAGIC) {
+            Self::Gzip
+        } else if bytes.starts_with(&ZSTD_MAGIC) {
+            Self::Zstd
+        } else {
+            Self::None
+        }
+    }
+
+    /// Detects the compression of a file from its extension
+    ///
+    /// # Example
+    /// ```
+    /// use graph6_rs::Compression;
+    /// assert_eq!(Compression::from_path("graphs.g6.gz"), Compression::Gzip);
+    /// assert_eq!(Compression::from_path("graphs.s6.zst"), Compression::Zstd);
+    /// assert_eq!(Compression::from_path("graphs.g6"), Compression::None);
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        match path.as_ref().extension().and_then(OsStr::to_str) {
+            Some("gz" | "gzip") => Self::Gzip,
+            Some("zst" | "zstd") => Self::Zstd,
+            _ => Self::None,
+        }
+    }
+
+    /// Returns true if the crate was built with the feature this compression needs
+    pub fn is_supported(&self) -> bool {
+        match self {
+            Self::None => true,
+            Self::Gzip => cfg!(feature = "gzip"),
+            Self::Zstd => cfg!(feature = "zstd"),
+        }
+    }
+}
+
+/// Wraps a buffered reader in a decoder matching its leading bytes
+pub fn decompress<'a, R: BufRead + 'a>(mut reader: R) -> io::Result<Box<dyn BufRead + 'a>> {
+    match Compression::from_magic(reader.fill_buf()?) {
+        Compression::None => Ok(Box::new(reader)),
+        Compression::Gzip => gzip_decoder(reader),
+        Compression::Zstd => zstd_decoder(reader),
+    }
+}
+
+/// Wraps a writer in an encoder for the given compression
+///
+/// The compressed stream must be completed with [`Encoder::finish`].
+pub fn compress<W: Write>(writer: W, compression: Compression) -> io::Result<Encoder<W>> {
+    let inner = match compression {
+        Compression::None => Inner::None(writer),
+        Compression::Gzip => gzip_encoder(writer)?,
+        Compression::Zstd => zstd_encoder(writer)?,
+    };
+    Ok(Encoder { inner: Some(inner) })
+}
+
+/// A writer which compresses everything written to it
+///
+/// Call [`Encoder::finish`] to write the end of the compressed stream and
+/// learn whether that succeeded. Dropping an unfinished encoder still
+/// attempts to complete the stream, but any error is lost.
+pub struct Encoder<W: Write> {
+    inner: Option<Inner<W>>,
+}
+impl<W: Write> Encoder<W> {
+    /// Completes the compressed stream, flushes the underlying writer and
+    /// returns it
+    pub fn finish(mut self) -> io::Result<W> {
+        // without compression features only the `None` variant exists
+        #[allow(clippy::infallible_destructuring_match)]
+        let mut writer = match self.inner.take().expect("encoder already finished") {
+            Inner::None(writer) => writer,
+            #[cfg(feature = "gzip")]
+            Inner::Gzip(encoder) => encoder.finish()?,
+            #[cfg(feature = "zstd")]
+            Inner::Zstd(encoder) => encoder.finish()?,
+        };
+        writer.flush()?;
+        Ok(writer)
+    }
+
+    /// Returns the active writer
+    fn writer(&mut self) -> &mut dyn Write {
+        match self.inner.as_mut().expect("encoder already finished") {
+            Inner::None(writer) => writer,
+            #[cfg(feature = "gzip")]
+            Inner::Gzip(encoder) => encoder,
+            #[cfg(feature = "zstd")]
+            Inner::Zstd(encoder) => encoder,
+        }
+    }
+}
+impl<W: Write> Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer().flush()
+    }
+}
+impl<W: Write> Drop for Encoder<W> {
+    fn drop(&mut self) {
+        // errors can only be reported by `finish`
+        let _: io::Result<()> = match &mut self.inner {
+            #[cfg(feature = "gzip")]
+            Some(Inner::Gzip(encoder)) => encoder.try_finish(),
+            #[cfg(feature = "zstd")]
+            Some(Inner::Zstd(encoder)) => encoder.do_finish(),
+            _ => Ok(()),
+        };
+    }
+}
+impl<W: Write> fmt::Debug for Encoder<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let compression = match &self.inner {
+            Some(Inner::None(_)) | None => Compression::None,
+            #[cfg(feature = "gzip")]
+            Some(Inner::Gzip(_)) => Compression::Gzip,
+            #[cfg(feature = "zstd")]
+            Some(Inner::Zstd(_)) => Compression::Zstd,
+        };
+        f.debug_struct("Encoder")
+            .field("compression", &compression)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The writers an [`Encoder`] can wrap
+enum Inner<W: Write> {
+    None(W),
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::write::GzEncoder<W>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::write::Encoder<'static, W>),
+}
+
+#[cfg(feature = "gzip")]
+fn gzip_decoder<'a, R: BufRead + 'a>(reader: R) -> io::Result<Box<dyn BufRead + 'a>> {
+    let decoder = flate2::bufread::MultiGzDecoder::new(reader);
+    Ok(Box::new(io::BufReader::new(decoder)))
+}
+
+#[cfg(not(feature = "gzip"))]
+fn gzip_decoder<'a, R: BufRead + 'a>(_reader: R) -> io::Result<Box<dyn BufRead + 'a>> {
+    Err(unsupported("gzip"))
+}
+
+#[cfg(feature = "gzip")]
+fn gzip_encoder<W: Write>(writer: W) -> io::Result<Inner<W>> {
+    let encoder = flate2::write::GzEncoder::new(writer, flate2::Compression::default());
+    Ok(Inner::Gzip(encoder))
+}
+
+#[cfg(not(feature = "gzip"))]
+fn gzip_encoder<W: Write>(_writer: W) -> io::Result<Inner<W>> {
+    Err(unsupported("gzip"))
+}
+
+#[cfg(feature = "zstd")]
+fn zstd_decoder<'a, R: BufRead + 'a>(reader: R) -> io::Result<Box<dyn BufRead + 'a>> {
+    let decoder = zstd::stream::read::Decoder::with_buffer(reader)?;
+    Ok(Box::new(io::BufReader::new(decoder)))
+}
+
+#[cfg(not(feature = "zstd"))]
+fn zstd_decoder<'a, R: BufRead + 'a>(_reader: R) -> io::Result<Box<dyn BufRead + 'a>> {
+    Err(unsupported("zstd"))
+}
+
+#[cfg(feature = "zstd")]
+fn zstd_encoder<W: Write>(writer: W) -> io::Result<Inner<W>> {
+    let encoder = zstd::stream::write::Encoder::new(writer, 0)?;
+    Ok(Inner::Zstd(encoder))
+}
+
+#[cfg(not(feature = "zstd"))]
+fn zstd_encoder<W: Write>(_writer: W) -> io::Result<Inner<W>> {
+    Err(unsupported("zstd"))
+}
+
+#[cfg(not(all(feature = "gzip", feature = "zstd")))]
+fn unsupported(feature: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("{} compression requires the `{}` feature", feature, feature),
+    )
+}
+
+#[cfg(test)]
+mod testing {
+    use super::{compress, decompress, Compression};
+    use std::io::{Read, Write};
+
+    fn roundtrip(compression: Compression) -> String {
+        let mut writer = compress(Vec::new(), compression).unwrap();
+        writer.write_all(b"A_\nBw\n").unwrap();
+        let bytes = writer.finish().unwrap();
+        assert_eq!(Compression::from_magic(&bytes), compression);
+        let mut decoded = String::new();
+        decompress(bytes.as_slice())
+            .unwrap()
+            .read_to_string(&mut decoded)
+            .unwrap();
+        decoded
+    }
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(Compression::from_path("a.g6.gz"), Compression::Gzip);
+        assert_eq!(Compression::from_path("a.d6.zstd"), Compression::Zstd);
+        assert_eq!(Compression::from_path("a.g6"), Compression::None);
+        assert_eq!(Compression::from_path("gz"), Compression::None);
+    }
+
+    #[test]
+    fn test_uncompressed() {
+        assert_eq!(roundtrip(Compression::None), "A_\nBw\n");
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_gzip() {
+        assert_eq!(roundtrip(Compression::Gzip), "A_\nBw\n");
+        assert!(Compression::Gzip.is_supported());
+    }
+
+    #[cfg(not(feature = "gzip"))]
+    #[test]
+    fn test_gzip_unsupported() {
+        let err = decompress(&[0x1f, 0x8b, 0x08][..]).err().unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+        assert!(!Compression::Gzip.is_supported());
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd() {
+        assert_eq!(roundtrip(Compression::Zstd), "A_\nBw\n");
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_finish_reports_errors() {
+        let mut buf = [0u8; 16];
+        let mut writer = compress(&mut buf[..], Compression::Gzip).unwrap();
+        writer.write_all(b"A_\nBw\n").unwrap();
+        let err = writer.finish().unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+    }
+}
@@ -1,3 +1,45 @@
+use crate::{
+    ConversionError, DimacsFormat, DotOptions, EdgeListOptions, ListgFormat, NetOptions, NpyDtype,
+    SvgOptions, Template, TikzOptions,
+};
+use std::{fmt, io};
+
+/// Node attributes which can be attached to exported vertices
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeAttribute {
+    /// Number of incident edges (in + out degree for directed graphs)
+    Degree,
+    /// Number of incoming arcs
+    InDegree,
+    /// Number of outgoing arcs
+    OutDegree,
+}
+impl NodeAttribute {
+    /// Returns the attribute name used in exported documents
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Degree => "degree",
+            Self::InDegree => "indegree",
+            Self::OutDegree => "outdegree",
+        }
+    }
+
+    /// Returns the attribute value for every vertex of a graph
+    pub fn values<G: GraphConversion + ?Sized>(&self, graph: &G) -> Vec<usize> {
+        let n = graph.size();
+        let bit_vec = graph.bit_vec();
+        let out_degree = |i: usize| (0..n).map(|j| bit_vec[i * n + j]).sum::<usize>();
+        let in_degree = |j: usize| (0..n).map(|i| bit_vec[i * n + j]).sum::<usize>();
+        (0..n)
+            .map(|v| match self {
+                Self::Degree if graph.is_directed() => in_degree(v) + out_degree(v),
+                Self::Degree | Self::OutDegree => out_degree(v),
+                Self::InDegree => in_degree(v),
+            })
+            .collect()
+    }
+}
+
 /// Conversion trait for graphs into various text graph formats
 pub trait GraphConversion {
     /// Returns the bitvector representation of the graph
@@ -9,40 +51,72 @@ pub trait GraphConversion {
     /// Returns true if the graph is directed
     fn is_directed(&self) -> bool;
 
-    /// Returns the graph in the DOT format
-    fn to_dot(&self, id: Option<usize>) -> String {
+    /// Returns the edges of the graph as vertex pairs
+    ///
+    /// Undirected edges are listed once from the upper triangle (`u <= v`),
+    /// directed edges are listed for every arc of the adjacency matrix.
+    fn edges(&self) -> Vec<(usize, usize)> {
         let n = self.size();
         let bit_vec = self.bit_vec();
-
-        let mut dot = String::new();
-
-        // include graph type
-        if self.is_directed() {
-            dot.push_str("digraph ");
-        } else {
-            dot.push_str("graph ");
+        let mut edges = Vec::new();
+        for i in 0..n {
+            let start = if self.is_directed() { 0 } else { i };
+            for j in start..n {
+                if bit_vec[i * n + j] == 1 {
+                    edges.push((i, j));
+                }
+            }
         }
+        edges
+    }
+
+    /// Returns the graph in the DOT format
+    fn to_dot(&self, id: Option<usize>) -> String {
+        render(|out| self.write_dot(out, id))
+    }
 
-        // include graph id
+    /// Writes the graph in the DOT format, see [`GraphConversion::to_dot`]
+    fn write_dot(&self, out: &mut dyn fmt::Write, id: Option<usize>) -> fmt::Result {
+        let mut options = DotOptions::new();
         if let Some(id) = id {
-            dot.push_str(&format!("graph_{} {{", id));
-        } else {
-            dot.push('{');
+            options = options.id(id);
         }
+        self.write_dot_with(out, &options)
+    }
 
-        // include edges
-        if self.is_directed() {
-            self.to_directed_dot(&mut dot, bit_vec, n);
-        } else {
-            self.to_undirected_dot(&mut dot, bit_vec, n);
+    /// Returns the graph in the DOT format with pinned vertex positions
+    ///
+    /// Every given position, e.g. from
+    /// [`Layout::positions`](crate::Layout::positions), is written as a
+    /// `pos="x,y!"` node attribute so that `neato`/`fdp` reproduce the same
+    /// picture. Graphviz reads these in inches; positions are scaled by the
+    /// default [`DotOptions::scale`] of two inches per unit.
+    fn to_dot_positioned(&self, id: Option<usize>, positions: &[(f64, f64)]) -> String {
+        let mut options = DotOptions::new().positions(positions);
+        if let Some(id) = id {
+            options = options.id(id);
         }
+        self.to_dot_with(&options)
+    }
 
-        // close graph
-        dot.push_str("\n}");
+    /// Returns the graph in the DOT format using configurable options
+    ///
+    /// See [`DotOptions`] for graph names, vertex labels and colours,
+    /// highlighted edges and layout hints.
+    fn to_dot_with(&self, options: &DotOptions) -> String {
+        render(|out| self.write_dot_with(out, options))
+    }
 
-        dot
+    /// Writes the graph in the DOT format using configurable options
+    fn write_dot_with(&self, out: &mut dyn fmt::Write, options: &DotOptions) -> fmt::Result {
+        crate::dot::write_dot(self, out, options)
     }
 
+    /// Appends the undirected edges of a DOT body
+    ///
+    /// [`GraphConversion::to_dot`] no longer calls this, so overriding it
+    /// has no effect.
+    #[deprecated(note = "not used by `to_dot`; customise DOT output with `DotOptions`")]
     fn to_undirected_dot(&self, dot: &mut String, bit_vec: &[usize], n: usize) {
         for i in 0..n {
             for j in i..n {
@@ -53,6 +127,11 @@ pub trait GraphConversion {
         }
     }
 
+    /// Appends the directed edges of a DOT body
+    ///
+    /// [`GraphConversion::to_dot`] no longer calls this, so overriding it
+    /// has no effect.
+    #[deprecated(note = "not used by `to_dot`; customise DOT output with `DotOptions`")]
     fn to_directed_dot(&self, dot: &mut String, bit_vec: &[usize], n: usize) {
         for i in 0..n {
             for j in 0..n {
@@ -63,56 +142,863 @@ pub trait GraphConversion {
         }
     }
 
+    /// Returns the graph as a Mermaid flowchart
+    ///
+    /// Vertices without any incident edges are listed on their own line.
+    fn to_mermaid(&self) -> String {
+        render(|out| self.write_mermaid(out))
+    }
+
+    /// Writes the graph as a Mermaid flowchart
+    fn write_mermaid(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let edges = self.edges();
+        let link = if self.is_directed() { "-->" } else { "---" };
+
+        writeln!(out, "graph LR")?;
+        let mut isolated = vec![true; self.size()];
+        for &(u, v) in &edges {
+            isolated[u] = false;
+            isolated[v] = false;
+        }
+        for (i, _) in isolated.iter().enumerate().filter(|(_, &iso)| iso) {
+            writeln!(out, "    {}", i)?;
+        }
+        for (u, v) in edges {
+            writeln!(out, "    {} {} {}", u, link, v)?;
+        }
+        Ok(())
+    }
+
     /// Returns the graph as an adjacency matrix
     fn to_adjmat(&self) -> String {
+        render(|out| self.write_adjmat(out))
+    }
+
+    /// Writes the graph as an adjacency matrix
+    fn write_adjmat(&self, out: &mut dyn fmt::Write) -> fmt::Result {
         let n = self.size();
         let bit_vec = self.bit_vec();
 
-        let mut adj = String::new();
         for i in 0..n {
             for j in 0..n {
-                adj.push_str(&format!("{}", bit_vec[i * n + j]));
+                write!(out, "{}", bit_vec[i * n + j])?;
                 if j < n - 1 {
-                    adj.push(' ');
+                    out.write_char(' ')?;
                 }
             }
-            adj.push('\n');
+            out.write_char('\n')?;
         }
-        adj
+        Ok(())
     }
 
     /// Returns the graph in a flat adjacency matrix
     fn to_flat(&self) -> String {
+        render(|out| self.write_flat(out))
+    }
+
+    /// Writes the graph as a flat adjacency matrix
+    fn write_flat(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        for bit in self.bit_vec() {
+            write!(out, "{}", bit)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the graph as an edge list with one edge per line
+    ///
+    /// Undirected edges are listed once, directed edges once per arc.
+    fn to_edge_list(&self, options: &EdgeListOptions) -> String {
+        render(|out| self.write_edge_list(out, options))
+    }
+
+    /// Writes the graph as an edge list with one edge per line
+    fn write_edge_list(&self, out: &mut dyn fmt::Write, options: &EdgeListOptions) -> fmt::Result {
+        let offset = options.one_based as usize;
+        let edges = self.edges();
+
+        if options.header {
+            writeln!(out, "{}{}{}", self.size(), options.separator, edges.len())?;
+        }
+        for (u, v) in edges {
+            writeln!(out, "{}{}{}", u + offset, options.separator, v + offset)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the graph as an adjacency list
+    ///
+    /// Each line lists a vertex followed by its neighbours (or its
+    /// out-neighbours for directed graphs).
+    fn to_adjacency_list(&self) -> String {
+        render(|out| self.write_adjacency_list(out))
+    }
+
+    /// Writes the graph as an adjacency list
+    fn write_adjacency_list(&self, out: &mut dyn fmt::Write) -> fmt::Result {
         let n = self.size();
         let bit_vec = self.bit_vec();
 
-        let mut flat = String::new();
         for i in 0..n {
+            write!(out, "{}:", i)?;
             for j in 0..n {
-                flat.push_str(&format!("{}", bit_vec[i * n + j]));
+                if bit_vec[i * n + j] == 1 {
+                    write!(out, " {}", j)?;
+                }
+            }
+            out.write_char('\n')?;
+        }
+        Ok(())
+    }
+
+    /// Returns the graph in the DIMACS graph format
+    ///
+    /// Vertices are numbered from 1 and every edge is listed once as
+    /// `u <= v`. DIMACS graphs are undirected, so digraphs are accepted only
+    /// if every arc has its reverse.
+    ///
+    /// # Errors
+    /// Returns an error if the graph is directed and its adjacency matrix
+    /// is not symmetric
+    fn to_dimacs(&self, format: DimacsFormat) -> Result<String, ConversionError> {
+        let mut dimacs = String::new();
+        self.write_dimacs(&mut dimacs, format)?;
+        Ok(dimacs)
+    }
+
+    /// Writes the graph in the DIMACS graph format
+    ///
+    /// # Errors
+    /// Returns [`ConversionError::NonSymmetricAdjacency`], before writing
+    /// anything, if the graph is directed and its adjacency matrix is not
+    /// symmetric, or [`ConversionError::Fmt`] if the output fails
+    fn write_dimacs(
+        &self,
+        out: &mut dyn fmt::Write,
+        format: DimacsFormat,
+    ) -> Result<(), ConversionError> {
+        if !is_symmetric(self) {
+            return Err(ConversionError::NonSymmetricAdjacency);
+        }
+        let n = self.size();
+        let bit_vec = self.bit_vec();
+        let edges: Vec<(usize, usize)> = (0..n)
+            .flat_map(|i| (i..n).map(move |j| (i, j)))
+            .filter(|&(i, j)| bit_vec[i * n + j] == 1)
+            .collect();
+
+        writeln!(out, "p {} {} {}", format.name(), n, edges.len())?;
+        for (u, v) in edges {
+            writeln!(out, "e {} {}", u + 1, v + 1)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the graph in the METIS graph format
+    ///
+    /// Vertices are numbered from 1 and self-loops are omitted, as METIS
+    /// does not allow them.
+    ///
+    /// # Errors
+    /// Returns an error if the graph is directed and its adjacency matrix
+    /// is not symmetric
+    fn to_metis(&self) -> Result<String, ConversionError> {
+        let mut metis = String::new();
+        self.write_metis(&mut metis)?;
+        Ok(metis)
+    }
+
+    /// Writes the graph in the METIS graph format
+    ///
+    /// # Errors
+    /// Returns [`ConversionError::NonSymmetricAdjacency`], before writing
+    /// anything, if the graph is directed and its adjacency matrix is not
+    /// symmetric, or [`ConversionError::Fmt`] if the output fails
+    fn write_metis(&self, out: &mut dyn fmt::Write) -> Result<(), ConversionError> {
+        if !is_symmetric(self) {
+            return Err(ConversionError::NonSymmetricAdjacency);
+        }
+        let n = self.size();
+        let bit_vec = self.bit_vec();
+        let m = (0..n)
+            .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+            .filter(|&(i, j)| bit_vec[i * n + j] == 1)
+            .count();
+
+        writeln!(out, "{} {}", n, m)?;
+        for i in 0..n {
+            let neighbours = (0..n)
+                .filter(|&j| j != i && bit_vec[i * n + j] == 1)
+                .map(|j| j + 1);
+            write_joined(out, neighbours, " ")?;
+            out.write_char('\n')?;
+        }
+        Ok(())
+    }
+
+    /// Returns the adjacency matrix in the Matrix Market coordinate format
+    ///
+    /// Undirected graphs are written as `symmetric` matrices listing only
+    /// the lower triangle, directed graphs as `general` matrices. Indices
+    /// are 1-based and only nonzero entries are listed.
+    fn to_matrix_market(&self) -> String {
+        render(|out| self.write_matrix_market(out))
+    }
+
+    /// Writes the adjacency matrix in the Matrix Market coordinate format
+    fn write_matrix_market(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let n = self.size();
+        let bit_vec = self.bit_vec();
+        let (symmetry, entries): (&str, Vec<(usize, usize)>) = if self.is_directed() {
+            ("general", self.edges())
+        } else {
+            let lower = (0..n)
+                .flat_map(|i| (0..=i).map(move |j| (i, j)))
+                .filter(|&(i, j)| bit_vec[i * n + j] == 1)
+                .collect();
+            ("symmetric", lower)
+        };
+
+        writeln!(out, "%%MatrixMarket matrix coordinate pattern {}", symmetry)?;
+        writeln!(out, "{} {} {}", n, n, entries.len())?;
+        for (i, j) in entries {
+            writeln!(out, "{} {}", i + 1, j + 1)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the graph as a TikZ `tikzpicture` environment
+    ///
+    /// Directed graphs are drawn with arrows on every arc.
+    fn to_tikz(&self, options: &TikzOptions) -> String {
+        render(|out| self.write_tikz(out, options))
+    }
+
+    /// Writes the graph as a TikZ `tikzpicture` environment
+    fn write_tikz(&self, out: &mut dyn fmt::Write, options: &TikzOptions) -> fmt::Result {
+        let positions = options.layout.positions(self);
+
+        writeln!(out, "\\begin{{tikzpicture}}")?;
+
+        // include nodes
+        for (i, (x, y)) in positions.iter().enumerate() {
+            let label = if options.labels {
+                i.to_string()
+            } else {
+                String::new()
+            };
+            writeln!(
+                out,
+                "  \\node[{}] (v{}) at ({:.3}, {:.3}) {{{}}};",
+                options.node_style,
+                i,
+                x * options.scale,
+                y * options.scale,
+                label
+            )?;
+        }
+
+        // include edges
+        let style = match (self.is_directed(), options.edge_style.is_empty()) {
+            (true, true) => "[->]".to_string(),
+            (true, false) => format!("[->, {}]", options.edge_style),
+            (false, true) => String::new(),
+            (false, false) => format!("[{}]", options.edge_style),
+        };
+        let n = self.size();
+        let bit_vec = self.bit_vec();
+        for (u, v) in self.edges() {
+            if u == v {
+                writeln!(out, "  \\draw{} (v{}) to[loop above] (v{});", style, u, v)?;
+            } else if self.is_directed() && bit_vec[v * n + u] == 1 {
+                // bend reciprocal arcs apart so both stay visible
+                writeln!(out, "  \\draw{} (v{}) to[bend left] (v{});", style, u, v)?;
+            } else {
+                writeln!(out, "  \\draw{} (v{}) -- (v{});", style, u, v)?;
             }
         }
-        flat
+
+        writeln!(out, "\\end{{tikzpicture}}")
+    }
+
+    /// Returns the graph rendered as a self-contained SVG image
+    ///
+    /// Arcs of directed graphs are drawn with arrowheads.
+    fn to_svg(&self, options: &SvgOptions) -> String {
+        render(|out| self.write_svg(out, options))
+    }
+
+    /// Writes the graph as a self-contained SVG image
+    fn write_svg(&self, out: &mut dyn fmt::Write, options: &SvgOptions) -> fmt::Result {
+        crate::svg::write_svg(self, out, options)
     }
 
     /// Returns the graph in the Pajek NET format
+    ///
+    /// Undirected graphs are written under `*Edges` with every edge listed
+    /// once, directed graphs under `*Arcs`.
     fn to_net(&self) -> String {
+        self.to_net_with(&NetOptions::new())
+    }
+
+    /// Writes the graph in the Pajek NET format, see [`GraphConversion::to_net`]
+    fn write_net(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.write_net_with(out, &NetOptions::new())
+    }
+
+    /// Returns the graph in the Pajek NET format with vertex coordinates
+    ///
+    /// Positions in `[-1, 1]`, as returned by
+    /// [`Layout::positions`](crate::Layout::positions), are mapped onto
+    /// Pajek's `[0, 1]` drawing area. Vertices without a position are
+    /// written without coordinates.
+    fn to_net_positioned(&self, positions: &[(f64, f64)]) -> String {
+        self.to_net_with(&NetOptions::new().positions(positions))
+    }
+
+    /// Returns the graph in the Pajek NET format using configurable options
+    ///
+    /// See [`NetOptions`] for vertex labels, coordinates and the compact
+    /// `*Edgeslist`/`*Arcslist` variant.
+    fn to_net_with(&self, options: &NetOptions) -> String {
+        render(|out| self.write_net_with(out, options))
+    }
+
+    /// Writes the graph in the Pajek NET format using configurable options
+    fn write_net_with(&self, out: &mut dyn fmt::Write, options: &NetOptions) -> fmt::Result {
         let n = self.size();
         let bit_vec = self.bit_vec();
+        let directed = self.is_directed();
 
-        let mut net = String::new();
-        net.push_str(&format!("*Vertices {}\n", n));
+        writeln!(out, "*Vertices {}", n)?;
         for i in 0..n {
-            net.push_str(&format!("{} \"{}\"\n", i + 1, i));
+            // Pajek has no quoting rule of its own; escapes follow igraph
+            let label = match options.labels.get(i) {
+                Some(label) => label.replace('\\', "\\\\").replace('"', "\\\""),
+                None => i.to_string(),
+            };
+            match options.positions.get(i) {
+                Some((x, y)) => writeln!(
+                    out,
+                    "{} \"{}\" {:.4} {:.4}",
+                    i + 1,
+                    label,
+                    (x + 1.0) / 2.0,
+                    (1.0 - y) / 2.0
+                )?,
+                None => writeln!(out, "{} \"{}\"", i + 1, label)?,
+            }
         }
-        net.push_str("*Arcs\n");
-        for i in 0..n {
-            for j in 0..n {
-                if bit_vec[i * n + j] == 1 {
-                    net.push_str(&format!("{} {}\n", i + 1, j + 1));
+
+        if options.compact {
+            out.write_str(if directed {
+                "*Arcslist\n"
+            } else {
+                "*Edgeslist\n"
+            })?;
+            for i in 0..n {
+                let start = if directed { 0 } else { i };
+                let neighbours: Vec<usize> = (start..n)
+                    .filter(|&j| bit_vec[i * n + j] == 1)
+                    .map(|j| j + 1)
+                    .collect();
+                if !neighbours.is_empty() {
+                    write!(out, "{} ", i + 1)?;
+                    write_joined(out, &neighbours, " ")?;
+                    out.write_char('\n')?;
+                }
+            }
+        } else {
+            out.write_str(if directed { "*Arcs\n" } else { "*Edges\n" })?;
+            for (u, v) in self.edges() {
+                writeln!(out, "{} {}", u + 1, v + 1)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the adjacency matrix as a NumPy `.npy` file
+    ///
+    /// The array has shape `(n, n)` and the given element type.
+    fn to_npy(&self, dtype: NpyDtype) -> Vec<u8> {
+        crate::npy::to_npy(self, dtype)
+    }
+
+    /// Writes the adjacency matrix as a NumPy `.npy` file
+    fn write_npy(&self, out: &mut dyn io::Write, dtype: NpyDtype) -> io::Result<()> {
+        crate::npy::write_npy(self, out, dtype)
+    }
+
+    /// Returns the graph as a Mathematica `Graph` expression
+    ///
+    /// Vertices are numbered from 1 and listed explicitly so that isolated
+    /// vertices are kept.
+    fn to_mathematica(&self) -> String {
+        render(|out| self.write_mathematica(out))
+    }
+
+    /// Writes the graph as a Mathematica `Graph` expression
+    fn write_mathematica(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let link = if self.is_directed() { "->" } else { "<->" };
+        out.write_str("Graph[{")?;
+        write_joined(out, 1..=self.size(), ", ")?;
+        out.write_str("}, {")?;
+        for (k, (u, v)) in self.edges().into_iter().enumerate() {
+            if k > 0 {
+                out.write_str(", ")?;
+            }
+            write!(out, "{} {} {}", u + 1, link, v + 1)?;
+        }
+        out.write_str("}]")
+    }
+
+    /// Returns the graph as a SageMath `Graph`/`DiGraph` constructor
+    ///
+    /// The graph is given as a dictionary of (out-)neighbours on vertices
+    /// numbered from 0, listing undirected edges once.
+    fn to_sage(&self) -> String {
+        render(|out| self.write_sage(out))
+    }
+
+    /// Writes the graph as a SageMath `Graph`/`DiGraph` constructor
+    fn write_sage(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let n = self.size();
+        let mut neighbours = vec![Vec::new(); n];
+        let mut loops = false;
+        for (u, v) in self.edges() {
+            neighbours[u].push(v);
+            loops |= u == v;
+        }
+        let constructor = if self.is_directed() {
+            "DiGraph"
+        } else {
+            "Graph"
+        };
+
+        write!(out, "{}({{", constructor)?;
+        for (u, adj) in neighbours.iter().enumerate() {
+            if u > 0 {
+                out.write_str(", ")?;
+            }
+            write!(out, "{}: [", u)?;
+            write_joined(out, adj, ", ")?;
+            out.write_char(']')?;
+        }
+        out.write_char('}')?;
+        if loops {
+            out.write_str(", loops=True")?;
+        }
+        out.write_char(')')
+    }
+
+    /// Returns the graph as a Maple `GraphTheory` constructor
+    ///
+    /// Vertices are numbered from 1. Self-loops are omitted as they are
+    /// not supported by `GraphTheory`.
+    fn to_maple(&self) -> String {
+        render(|out| self.write_maple(out))
+    }
+
+    /// Writes the graph as a Maple `GraphTheory` constructor
+    fn write_maple(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let directed = self.is_directed();
+        let constructor = if directed { "Digraph" } else { "Graph" };
+
+        write!(out, "GraphTheory:-{}({}, {{", constructor, self.size())?;
+        let edges = self.edges().into_iter().filter(|(u, v)| u != v);
+        for (k, (u, v)) in edges.enumerate() {
+            if k > 0 {
+                out.write_str(", ")?;
+            }
+            if directed {
+                write!(out, "[{}, {}]", u + 1, v + 1)?;
+            } else {
+                write!(out, "{{{}, {}}}", u + 1, v + 1)?;
+            }
+        }
+        out.write_str("})")
+    }
+
+    /// Returns the graph as printed by nauty's `listg`
+    ///
+    /// `number` is the 1-based position of the graph shown in the
+    /// `Graph k, order n.` header. Vertices are numbered from 0 and the
+    /// adjacency and edge lists are wrapped at 78 columns, as `listg` does
+    /// with its default `-l78`.
+    fn to_listg(&self, format: ListgFormat, number: usize) -> String {
+        render(|out| self.write_listg(out, format, number))
+    }
+
+    /// Writes the graph as printed by nauty's `listg`
+    fn write_listg(
+        &self,
+        out: &mut dyn fmt::Write,
+        format: ListgFormat,
+        number: usize,
+    ) -> fmt::Result {
+        let n = self.size();
+        write!(out, "\nGraph {}, order {}.\n", number, n)?;
+        let bit_vec = self.bit_vec();
+        let directed = self.is_directed();
+        let adjacent =
+            |u: usize, v: usize| bit_vec[u * n + v] == 1 || (!directed && bit_vec[v * n + u] == 1);
+        match format {
+            ListgFormat::List => {
+                for u in 0..n {
+                    let label = format!("{:3} :", u);
+                    out.write_str(&label)?;
+                    write_wrapped(out, (0..n).filter(|&v| adjacent(u, v)), label.len())?;
+                    out.write_str(";\n")?;
                 }
             }
+            ListgFormat::Matrix => {
+                for u in 0..n {
+                    for v in 0..n {
+                        out.write_char(if adjacent(u, v) { '1' } else { '0' })?;
+                    }
+                    out.write_char('\n')?;
+                }
+            }
+            ListgFormat::Edges => {
+                let edges = self.edges();
+                writeln!(out, "{} {}", n, edges.len())?;
+                let mut len = 0;
+                for (u, v) in edges {
+                    // nauty starts a new line once fewer than 10 columns are left
+                    if len > LISTG_LINE_LENGTH - 10 {
+                        out.write_char('\n')?;
+                        len = 0;
+                    }
+                    if len > 0 {
+                        out.write_str("  ")?;
+                        len += 2;
+                    }
+                    let edge = format!("{} {}", u, v);
+                    out.write_str(&edge)?;
+                    len += edge.len();
+                }
+                // the edge list is always closed, even when it is empty
+                out.write_char('\n')?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a dreadnaut command block that enters the graph
+    ///
+    /// This is the block written by `listg -d`: vertices are numbered from
+    /// 0 (`$=0`) and each vertex's line lists all its neighbours, or its
+    /// out-neighbours for digraphs (`d`), wrapped at 78 columns.
+    fn to_dreadnaut(&self) -> String {
+        render(|out| self.write_dreadnaut(out))
+    }
+
+    /// Writes a dreadnaut command block that enters the graph
+    fn write_dreadnaut(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let n = self.size();
+        let bit_vec = self.bit_vec();
+        let directed = self.is_directed();
+        if directed {
+            out.write_str("d\n")?;
+        }
+        writeln!(out, "n={} $=0 g", n)?;
+        // `;` moves on to the next vertex, so the last list ends with `.` only
+        for u in 0..n {
+            let adjacent = (0..n)
+                .filter(|&v| bit_vec[u * n + v] == 1 || (!directed && bit_vec[v * n + u] == 1));
+            write_wrapped(out, adjacent, 0)?;
+            out.write_str(if u + 1 < n { ";\n" } else { ".\n" })?;
+        }
+        if n == 0 {
+            out.write_str(".\n")?;
+        }
+        Ok(())
+    }
+
+    /// Returns the graph in a user-defined format
+    ///
+    /// See [`Template`] for the available placeholders.
+    fn to_template(&self, template: &Template) -> String {
+        render(|out| self.write_template(out, template))
+    }
+
+    /// Writes the graph in a user-defined format
+    fn write_template(&self, out: &mut dyn fmt::Write, template: &Template) -> fmt::Result {
+        crate::template::write_template(self, out, template)
+    }
+
+    /// Returns the graph in the GML format
+    fn to_gml(&self) -> String {
+        render(|out| self.write_gml(out))
+    }
+
+    /// Writes the graph in the GML format
+    fn write_gml(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_str("graph [\n")?;
+        writeln!(out, "  directed {}", self.is_directed() as usize)?;
+        for i in 0..self.size() {
+            write!(out, "  node [\n    id {}\n    label \"{}\"\n  ]\n", i, i)?;
+        }
+        for (u, v) in self.edges() {
+            write!(out, "  edge [\n    source {}\n    target {}\n  ]\n", u, v)?;
+        }
+        out.write_str("]\n")
+    }
+
+    /// Returns the graph as node-link JSON
+    ///
+    /// # Arguments
+    /// * `layout` - The JSON layout to produce (networkx node-link or Cytoscape.js)
+    #[cfg(feature = "serde")]
+    fn to_json(&self, layout: crate::JsonLayout) -> String {
+        crate::json::to_json(self, layout)
+    }
+
+    /// Writes the graph as node-link JSON
+    #[cfg(feature = "serde")]
+    fn write_json(&self, out: &mut dyn io::Write, layout: crate::JsonLayout) -> io::Result<()> {
+        crate::json::write_json(self, out, layout)
+    }
+
+    /// Returns the graph as a GraphML document
+    fn to_graphml(&self, id: Option<usize>) -> String {
+        render(|out| self.write_graphml(out, id))
+    }
+
+    /// Writes the graph as a GraphML document
+    fn write_graphml(&self, out: &mut dyn fmt::Write, id: Option<usize>) -> fmt::Result {
+        out.write_str(GRAPHML_HEADER)?;
+        let graph_id = id.map(|id| format!("graph_{}", id));
+        write_graphml_graph(out, self, graph_id.as_deref(), "n")?;
+        out.write_str(GRAPHML_FOOTER)
+    }
+
+    /// Returns the graph in the GEXF format used by Gephi
+    ///
+    /// # Arguments
+    /// * `attributes` - Node attributes to include for every vertex
+    fn to_gexf(&self, attributes: &[NodeAttribute]) -> String {
+        render(|out| self.write_gexf(out, attributes))
+    }
+
+    /// Writes the graph in the GEXF format used by Gephi
+    fn write_gexf(&self, out: &mut dyn fmt::Write, attributes: &[NodeAttribute]) -> fmt::Result {
+        let edge_type = if self.is_directed() {
+            "directed"
+        } else {
+            "undirected"
+        };
+        let values: Vec<Vec<usize>> = attributes.iter().map(|attr| attr.values(self)).collect();
+
+        out.write_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n")?;
+        out.write_str("<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n")?;
+        writeln!(
+            out,
+            "  <graph defaultedgetype=\"{}\" mode=\"static\">",
+            edge_type
+        )?;
+
+        // include attribute declarations
+        if !attributes.is_empty() {
+            out.write_str("    <attributes class=\"node\">\n")?;
+            for (k, attr) in attributes.iter().enumerate() {
+                writeln!(
+                    out,
+                    "      <attribute id=\"{}\" title=\"{}\" type=\"integer\"/>",
+                    k,
+                    attr.name()
+                )?;
+            }
+            out.write_str("    </attributes>\n")?;
+        }
+
+        // include nodes
+        out.write_str("    <nodes>\n")?;
+        for i in 0..self.size() {
+            if attributes.is_empty() {
+                writeln!(out, "      <node id=\"{}\" label=\"{}\"/>", i, i)?;
+                continue;
+            }
+            writeln!(out, "      <node id=\"{}\" label=\"{}\">", i, i)?;
+            out.write_str("        <attvalues>\n")?;
+            for (k, values) in values.iter().enumerate() {
+                writeln!(
+                    out,
+                    "          <attvalue for=\"{}\" value=\"{}\"/>",
+                    k, values[i]
+                )?;
+            }
+            out.write_str("        </attvalues>\n")?;
+            out.write_str("      </node>\n")?;
         }
-        net
+        out.write_str("    </nodes>\n")?;
+
+        // include edges
+        out.write_str("    <edges>\n")?;
+        for (k, (u, v)) in self.edges().into_iter().enumerate() {
+            writeln!(
+                out,
+                "      <edge id=\"{}\" source=\"{}\" target=\"{}\"/>",
+                k, u, v
+            )?;
+        }
+        out.write_str("    </edges>\n")?;
+
+        // close graph
+        out.write_str("  </graph>\n</gexf>\n")
+    }
+}
+
+/// Lets borrowed graphs be passed wherever owned graphs are accepted
+impl<G: GraphConversion + ?Sized> GraphConversion for &G {
+    fn bit_vec(&self) -> &[usize] {
+        (**self).bit_vec()
+    }
+
+    fn size(&self) -> usize {
+        (**self).size()
+    }
+
+    fn is_directed(&self) -> bool {
+        (**self).is_directed()
+    }
+}
+
+/// Collects the output of a writer-based conversion into a `String`
+pub(crate) fn render<F>(write: F) -> String
+where
+    F: FnOnce(&mut dyn fmt::Write) -> fmt::Result,
+{
+    let mut out = String::new();
+    // writing into a String cannot fail
+    write(&mut out).expect("conversion into a String failed");
+    out
+}
+
+/// Line length used by nauty's `listg` unless `-l` is given
+const LISTG_LINE_LENGTH: usize = 78;
+
+/// Writes vertices as ` v` entries wrapped like nauty's `putset`
+///
+/// `len` is the length of the text already on the line. A line is broken
+/// before an entry that would reach [`LISTG_LINE_LENGTH`], and continuation
+/// lines are indented by three spaces.
+fn write_wrapped<I>(out: &mut dyn fmt::Write, vertices: I, mut len: usize) -> fmt::Result
+where
+    I: IntoIterator<Item = usize>,
+{
+    for v in vertices {
+        let vertex = v.to_string();
+        if len + vertex.len() + 1 >= LISTG_LINE_LENGTH {
+            out.write_str("\n   ")?;
+            len = 3;
+        }
+        write!(out, " {}", vertex)?;
+        len += vertex.len() + 1;
+    }
+    Ok(())
+}
+
+/// Returns true if the graph is undirected or its adjacency matrix is symmetric
+fn is_symmetric<G: GraphConversion + ?Sized>(graph: &G) -> bool {
+    let n = graph.size();
+    let bit_vec = graph.bit_vec();
+    !graph.is_directed() || (0..n).all(|i| (0..i).all(|j| bit_vec[i * n + j] == bit_vec[j * n + i]))
+}
+
+/// Writes the items separated by `separator`
+fn write_joined<I>(out: &mut dyn fmt::Write, items: I, separator: &str) -> fmt::Result
+where
+    I: IntoIterator,
+    I::Item: fmt::Display,
+{
+    for (k, item) in items.into_iter().enumerate() {
+        if k > 0 {
+            out.write_str(separator)?;
+        }
+        write!(out, "{}", item)?;
+    }
+    Ok(())
+}
+
+const GRAPHML_HEADER: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\" \
+xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+xsi:schemaLocation=\"http://graphml.graphdrawing.org/xmlns \
+http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd\">
+";
+
+const GRAPHML_FOOTER: &str = "</graphml>\n";
+
+/// Returns many graphs as a single GraphML document
+///
+/// Each graph is written as its own `<graph>` element with id `graph_k`,
+/// and node ids are prefixed with the graph index to keep them unique
+/// within the document.
+///
+/// # Example
+/// ```
+/// use graph6_rs::{to_graphml_document, Graph};
+/// let graphs = vec![Graph::from_g6("A_").unwrap(), Graph::from_g6("Bw").unwrap()];
+/// let graphml = to_graphml_document(&graphs);
+/// assert!(graphml.contains("<graph id=\"graph_1\" edgedefault=\"undirected\">"));
+/// assert!(graphml.contains("<edge source=\"g1n1\" target=\"g1n2\"/>"));
+/// ```
+pub fn to_graphml_document<I>(graphs: I) -> String
+where
+    I: IntoIterator,
+    I::Item: GraphConversion,
+{
+    render(|out| write_graphml_document(out, graphs))
+}
+
+/// Writes many graphs as a single GraphML document
+///
+/// Each `<graph>` element is written as soon as its graph is produced, so
+/// `graphs` can be a [`GraphReader`](crate::GraphReader) iterator over a
+/// large file. See [`to_graphml_document`] for the layout.
+pub fn write_graphml_document<I>(out: &mut dyn fmt::Write, graphs: I) -> fmt::Result
+where
+    I: IntoIterator,
+    I::Item: GraphConversion,
+{
+    out.write_str(GRAPHML_HEADER)?;
+    for (k, graph) in graphs.into_iter().enumerate() {
+        let graph_id = format!("graph_{}", k);
+        let prefix = format!("g{}n", k);
+        write_graphml_graph(out, &graph, Some(&graph_id), &prefix)?;
+    }
+    out.write_str(GRAPHML_FOOTER)
+}
+
+/// Writes a single `<graph>` element of a GraphML document
+fn write_graphml_graph<G: GraphConversion + ?Sized>(
+    out: &mut dyn fmt::Write,
+    graph: &G,
+    id: Option<&str>,
+    prefix: &str,
+) -> fmt::Result {
+    let edgedefault = if graph.is_directed() {
+        "directed"
+    } else {
+        "undirected"
+    };
+    match id {
+        Some(id) => writeln!(
+            out,
+            "  <graph id=\"{}\" edgedefault=\"{}\">",
+            id, edgedefault
+        )?,
+        None => writeln!(out, "  <graph edgedefault=\"{}\">", edgedefault)?,
+    }
+    for i in 0..graph.size() {
+        writeln!(out, "    <node id=\"{}{}\"/>", prefix, i)?;
+    }
+    for (u, v) in graph.edges() {
+        writeln!(
+            out,
+            "    <edge source=\"{}{}\" target=\"{}{}\"/>",
+            prefix, u, prefix, v
+        )?;
     }
+    out.write_str("  </graph>\n")
 }
@@ -30,10 +30,10 @@ impl DiGraph {
         let bytes = repr.as_bytes();
         Self::valid_digraph(bytes)?;
         let n = get_size(bytes, 1)?;
-        let Some(bit_vec) = Self::build_bitvector(bytes, n) else {
-            return Err(IOError::NonCanonicalEncoding);
-        };
-        Ok(Self { bit_vec, n })
+        match Self::build_bitvector(bytes, n) {
+            Some(bit_vec) if bit_vec.len() == n * n => Ok(Self { bit_vec, n }),
+            _ => Err(IOError::NonCanonicalEncoding),
+        }
     }
 
     /// Creates a new DiGraph from a flattened adjacency matrix
@@ -63,7 +63,7 @@ impl DiGraph {
 
     /// Validates graph6 directed representation
     fn valid_digraph(repr: &[u8]) -> Result<bool, IOError> {
-        if repr[0] == b'&' {
+        if repr.first() == Some(&b'&') {
             Ok(true)
         } else {
             Err(IOError::InvalidDigraphHeader)
@@ -74,8 +74,7 @@ impl DiGraph {
     /// representing the adjaceny matrix of the graph
     fn build_bitvector(bytes: &[u8], n: usize) -> Option<Vec<usize>> {
         let bv_len = n * n;
-        let bit_vec = fill_bitvector(bytes, bv_len, 2);
-        bit_vec
+        fill_bitvector(bytes, bv_len, 2)
     }
 }
 
@@ -97,7 +96,10 @@ impl WriteGraph for DiGraph {}
 
 #[cfg(test)]
 mod testing {
-    use crate::WriteGraph;
+    use crate::{
+        DimacsFormat, EdgeListOptions, ListgFormat, NodeAttribute, SvgOptions, TikzOptions,
+        WriteGraph,
+    };
 
     use super::GraphConversion;
 
@@ -207,6 +209,14 @@ mod testing {
         assert_eq!(net, "*Vertices 2\n1 \"0\"\n2 \"1\"\n*Arcs\n2 1\n");
     }
 
+    #[test]
+    fn test_to_net_compact() {
+        let repr = r"&B\o";
+        let graph = super::DiGraph::from_d6(repr).unwrap();
+        let net = graph.to_net_with(&crate::NetOptions::new().compact(true));
+        assert!(net.ends_with("*Arcslist\n1 2 3\n2 1 3\n3 1 2\n"));
+    }
+
     #[test]
     fn test_to_flat() {
         let repr = r"&AG";
@@ -238,4 +248,248 @@ mod testing {
         let graph6 = graph.write_graph();
         assert_eq!(graph6, repr);
     }
+
+    #[test]
+    fn test_edges() {
+        let graph = super::DiGraph::from_d6(r"&B\o").unwrap();
+        assert_eq!(
+            graph.edges(),
+            vec![(0, 1), (0, 2), (1, 0), (1, 2), (2, 0), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_to_graphml() {
+        let repr = r"&AG";
+        let graph = super::DiGraph::from_d6(repr).unwrap();
+        let graphml = graph.to_graphml(Some(1));
+        assert!(graphml.ends_with(
+            "  <graph id=\"graph_1\" edgedefault=\"directed\">\n    <node id=\"n0\"/>\n    <node id=\"n1\"/>\n    <edge source=\"n1\" target=\"n0\"/>\n  </graph>\n</graphml>\n"
+        ));
+    }
+
+    #[test]
+    fn test_to_gexf() {
+        let repr = r"&AG";
+        let graph = super::DiGraph::from_d6(repr).unwrap();
+        let gexf = graph.to_gexf(&[NodeAttribute::InDegree, NodeAttribute::OutDegree]);
+        assert!(gexf.contains("<graph defaultedgetype=\"directed\" mode=\"static\">"));
+        assert!(gexf.contains("<edge id=\"0\" source=\"1\" target=\"0\"/>"));
+        assert!(gexf.contains(
+            "<node id=\"0\" label=\"0\">\n        <attvalues>\n          <attvalue for=\"0\" value=\"1\"/>\n          <attvalue for=\"1\" value=\"0\"/>"
+        ));
+    }
+
+    #[test]
+    fn test_node_attribute_values() {
+        let graph = super::DiGraph::from_d6(r"&AG").unwrap();
+        assert_eq!(NodeAttribute::Degree.values(&graph), vec![1, 1]);
+        assert_eq!(NodeAttribute::InDegree.values(&graph), vec![1, 0]);
+        assert_eq!(NodeAttribute::OutDegree.values(&graph), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_to_gml() {
+        let repr = r"&AG";
+        let graph = super::DiGraph::from_d6(repr).unwrap();
+        let gml = graph.to_gml();
+        assert!(gml.starts_with("graph [\n  directed 1\n"));
+        assert!(gml.ends_with("  edge [\n    source 1\n    target 0\n  ]\n]\n"));
+        assert_eq!(gml.matches("edge [").count(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_json() {
+        let repr = r"&AG";
+        let graph = super::DiGraph::from_d6(repr).unwrap();
+        let json = graph.to_json(crate::JsonLayout::NodeLink);
+        assert_eq!(
+            json,
+            r#"{"directed":true,"multigraph":false,"graph":{},"nodes":[{"id":0},{"id":1}],"links":[{"source":1,"target":0}]}"#
+        );
+    }
+
+    #[test]
+    fn test_to_edge_list() {
+        let repr = r"&AG";
+        let graph = super::DiGraph::from_d6(repr).unwrap();
+        let list = graph.to_edge_list(&EdgeListOptions::new().one_based(true));
+        assert_eq!(list, "2 1\n");
+    }
+
+    #[test]
+    fn test_to_adjacency_list() {
+        let repr = r"&AG";
+        let graph = super::DiGraph::from_d6(repr).unwrap();
+        let adj = graph.to_adjacency_list();
+        assert_eq!(adj, "0:\n1: 0\n");
+    }
+
+    #[test]
+    fn test_to_dimacs() {
+        let repr = r"&AG";
+        let graph = super::DiGraph::from_d6(repr).unwrap();
+        let dimacs = graph.to_dimacs(DimacsFormat::Edge);
+        assert_eq!(dimacs, Err(crate::ConversionError::NonSymmetricAdjacency));
+    }
+
+    #[test]
+    fn test_to_dimacs_symmetric() {
+        let graph = super::DiGraph::from_adj(&[0, 1, 1, 0]).unwrap();
+        let dimacs = graph.to_dimacs(DimacsFormat::Edge).unwrap();
+        assert_eq!(dimacs, "p edge 2 1\ne 1 2\n");
+        let graph = super::DiGraph::from_d6(r"&B\o").unwrap();
+        let dimacs = graph.to_dimacs(DimacsFormat::Col).unwrap();
+        assert_eq!(dimacs, "p col 3 3\ne 1 2\ne 1 3\ne 2 3\n");
+    }
+
+    #[test]
+    fn test_to_metis_symmetric() {
+        let repr = r"&B\o";
+        let graph = super::DiGraph::from_d6(repr).unwrap();
+        let metis = graph.to_metis().unwrap();
+        assert_eq!(metis, "3 3\n2 3\n1 3\n1 2\n");
+    }
+
+    #[test]
+    fn test_to_metis_nonsymmetric() {
+        let repr = r"&AG";
+        let graph = super::DiGraph::from_d6(repr).unwrap();
+        let metis = graph.to_metis();
+        assert_eq!(metis, Err(crate::ConversionError::NonSymmetricAdjacency));
+        let mut out = String::new();
+        assert!(graph.write_metis(&mut out).is_err());
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_to_matrix_market() {
+        let repr = r"&AG";
+        let graph = super::DiGraph::from_d6(repr).unwrap();
+        let mm = graph.to_matrix_market();
+        assert_eq!(
+            mm,
+            "%%MatrixMarket matrix coordinate pattern general\n2 2 1\n2 1\n"
+        );
+    }
+
+    #[test]
+    fn test_to_tikz() {
+        let repr = r"&AG";
+        let graph = super::DiGraph::from_d6(repr).unwrap();
+        let tikz = graph.to_tikz(&TikzOptions::new().edge_style("red"));
+        assert!(tikz.contains("\\draw[->, red] (v1) -- (v0);"));
+    }
+
+    #[test]
+    fn test_to_tikz_reciprocal() {
+        let graph = super::DiGraph::from_adj(&[0, 1, 1, 0]).unwrap();
+        let tikz = graph.to_tikz(&TikzOptions::new());
+        assert!(tikz.contains("\\draw[->] (v0) to[bend left] (v1);"));
+        assert!(tikz.contains("\\draw[->] (v1) to[bend left] (v0);"));
+        assert!(!tikz.contains("--"));
+    }
+
+    #[test]
+    fn test_to_mermaid() {
+        let repr = r"&AG";
+        let graph = super::DiGraph::from_d6(repr).unwrap();
+        let mermaid = graph.to_mermaid();
+        assert_eq!(mermaid, "graph LR\n    1 --> 0\n");
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let repr = r"&AG";
+        let graph = super::DiGraph::from_d6(repr).unwrap();
+        let svg = graph.to_svg(&SvgOptions::new().size(100.0));
+        assert!(svg.contains("<marker id=\"arrow\""));
+        assert!(svg.contains(
+            "<line x1=\"20.0\" y1=\"50.0\" x2=\"70.0\" y2=\"50.0\" stroke=\"black\" marker-end=\"url(#arrow)\"/>"
+        ));
+    }
+
+    #[test]
+    fn test_to_dot_positioned() {
+        let repr = r"&AG";
+        let graph = super::DiGraph::from_d6(repr).unwrap();
+        let positions = crate::Layout::Circular.positions(&graph);
+        let dot = graph.to_dot_positioned(Some(1), &positions);
+        assert!(dot.starts_with("digraph graph_1 {\n0 [pos=\"2.000,0.000!\"];\n"));
+        assert!(dot.ends_with("1 -> 0;\n}"));
+    }
+
+    #[test]
+    fn test_to_npy() {
+        let repr = r"&AG";
+        let graph = super::DiGraph::from_d6(repr).unwrap();
+        let npy = graph.to_npy(crate::NpyDtype::F32);
+        assert_eq!(&npy[128..136], &[0; 8]);
+        assert_eq!(&npy[136..140], &1f32.to_le_bytes());
+    }
+
+    #[test]
+    fn test_to_mathematica() {
+        let repr = r"&AG";
+        let graph = super::DiGraph::from_d6(repr).unwrap();
+        assert_eq!(graph.to_mathematica(), "Graph[{1, 2}, {2 -> 1}]");
+    }
+
+    #[test]
+    fn test_to_sage() {
+        let graph = super::DiGraph::from_adj(&[1, 1, 0, 0]).unwrap();
+        assert_eq!(graph.to_sage(), "DiGraph({0: [0, 1], 1: []}, loops=True)");
+    }
+
+    #[test]
+    fn test_to_maple() {
+        let graph = super::DiGraph::from_adj(&[1, 1, 0, 0]).unwrap();
+        assert_eq!(graph.to_maple(), "GraphTheory:-Digraph(2, {[1, 2]})");
+    }
+
+    #[test]
+    fn test_to_listg() {
+        let graph = super::DiGraph::from_adj(&[0, 1, 0, 0]).unwrap();
+        let listg = graph.to_listg(ListgFormat::List, 1);
+        assert_eq!(listg, "\nGraph 1, order 2.\n  0 : 1;\n  1 :;\n");
+        let listg = graph.to_listg(ListgFormat::Matrix, 1);
+        assert_eq!(listg, "\nGraph 1, order 2.\n01\n00\n");
+        let listg = graph.to_listg(ListgFormat::Edges, 1);
+        assert_eq!(listg, "\nGraph 1, order 2.\n2 1\n0 1\n");
+    }
+
+    #[test]
+    fn test_to_dreadnaut() {
+        let graph = super::DiGraph::from_adj(&[0, 0, 1, 0]).unwrap();
+        assert_eq!(graph.to_dreadnaut(), "d\nn=2 $=0 g\n;\n 0.\n");
+    }
+
+    #[test]
+    fn test_write_matches_to() {
+        let graph = super::DiGraph::from_d6(r"&C]|w").unwrap();
+        let mut out = String::new();
+        graph
+            .write_edge_list(&mut out, &EdgeListOptions::new())
+            .unwrap();
+        assert_eq!(out, graph.to_edge_list(&EdgeListOptions::new()));
+        out.clear();
+        graph
+            .write_gexf(&mut out, &[NodeAttribute::InDegree])
+            .unwrap();
+        assert_eq!(out, graph.to_gexf(&[NodeAttribute::InDegree]));
+        out.clear();
+        graph.write_listg(&mut out, ListgFormat::Edges, 3).unwrap();
+        assert_eq!(out, graph.to_listg(ListgFormat::Edges, 3));
+    }
+
+    #[test]
+    fn test_to_template() {
+        let graph = super::DiGraph::from_d6("&AG").unwrap();
+        let template = crate::Template::new()
+            .header("{n} {directed}\n")
+            .edge("{u}>{v};")
+            .one_based(true);
+        assert_eq!(graph.to_template(&template), "2 true\n2>1;");
+    }
 }
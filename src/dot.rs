@@ -0,0 +1,526 @@
+use crate::{conversion::render, GraphConversion};
+use std::fmt;
+
+/// Colours used by [`DotOptions::colouring`] for the colour classes
+const PALETTE: [&str; 10] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f",
+    "#bcbd22", "#17becf",
+];
+
+/// Options for the DOT export
+///
+/// The default options reproduce the output of
+/// [`GraphConversion::to_dot`] without an id.
+///
+/// # Example
+/// ```
+/// use graph6_rs::{DotOptions, Graph, GraphConversion};
+/// let graph = Graph::from_g6("Bw").unwrap();
+/// let options = DotOptions::new()
+///     .name("triangle")
+///     .node_attr("shape", "circle")
+///     .colouring(&[0, 1, 2])
+///     .highlight_edges(&[(0, 1)]);
+/// let dot = graph.to_dot_with(&options);
+/// assert!(dot.starts_with("graph triangle {\nnode [shape=\"circle\"];"));
+/// assert!(dot.contains("0 -- 1 [color=\"red\", penwidth=\"2\"];"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DotOptions {
+    name: Option<String>,
+    labels: Vec<String>,
+    colours: Vec<String>,
+    positions: Vec<(f64, f64)>,
+    scale: f64,
+    graph_attrs: Vec<(String, String)>,
+    node_attrs: Vec<(String, String)>,
+    edge_attrs: Vec<(String, String)>,
+    highlighted: Vec<(usize, usize)>,
+    highlight_attrs: Vec<(String, String)>,
+    ranks: Vec<Vec<usize>>,
+}
+impl Default for DotOptions {
+    fn default() -> Self {
+        Self {
+            name: None,
+            labels: Vec::new(),
+            colours: Vec::new(),
+            positions: Vec::new(),
+            scale: 2.0,
+            graph_attrs: Vec::new(),
+            node_attrs: Vec::new(),
+            edge_attrs: Vec::new(),
+            highlighted: Vec::new(),
+            highlight_attrs: Vec::new(),
+            ranks: Vec::new(),
+        }
+    }
+}
+impl DotOptions {
+    /// Creates the default options (anonymous graph, bare integer vertices)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the name of the graph
+    ///
+    /// Names other than plain identifiers are quoted and escaped.
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Names the graph `graph_{id}`, matching [`GraphConversion::to_dot`]
+    pub fn id(mut self, id: usize) -> Self {
+        self.name = Some(format!("graph_{}", id));
+        self
+    }
+
+    /// Sets the label of every vertex, in vertex order
+    pub fn labels<I, S>(mut self, labels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.labels = labels.into_iter().map(|label| label.to_string()).collect();
+        self
+    }
+
+    /// Sets the fill colour of every vertex, in vertex order
+    pub fn colours<I, S>(mut self, colours: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.colours = colours
+            .into_iter()
+            .map(|colour| colour.to_string())
+            .collect();
+        self
+    }
+
+    /// Colours vertices by class (e.g. from a colouring or partition)
+    ///
+    /// Classes are mapped onto a fixed palette of ten colours.
+    pub fn colouring(mut self, classes: &[usize]) -> Self {
+        self.colours = classes
+            .iter()
+            .map(|class| PALETTE[class % PALETTE.len()].to_string())
+            .collect();
+        self
+    }
+
+    /// Pins vertices to positions, e.g. from [`Layout::positions`](crate::Layout::positions)
+    ///
+    /// Graphviz reads pinned positions in inches, so coordinates are
+    /// multiplied by [`DotOptions::scale`] first.
+    pub fn positions(mut self, positions: &[(f64, f64)]) -> Self {
+        self.positions = positions.to_vec();
+        self
+    }
+
+    /// Sets the inches per layout unit for pinned positions (default 2)
+    ///
+    /// Layouts fit within `[-1, 1]`, so the default spreads the drawing over
+    /// four inches, leaving room for Graphviz's 0.75 inch nodes.
+    pub fn scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Adds a graph attribute such as a layout hint (`rankdir`, `layout`, ...)
+    pub fn graph_attr(mut self, key: &str, value: &str) -> Self {
+        self.graph_attrs.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Adds a default attribute for every node
+    pub fn node_attr(mut self, key: &str, value: &str) -> Self {
+        self.node_attrs.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Adds a default attribute for every edge
+    pub fn edge_attr(mut self, key: &str, value: &str) -> Self {
+        self.edge_attrs.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Highlights a set of edges
+    ///
+    /// Highlighted edges are drawn red and thick unless attributes are set
+    /// with [`DotOptions::highlight_attr`]. For undirected graphs the order
+    /// of the two vertices does not matter.
+    pub fn highlight_edges(mut self, edges: &[(usize, usize)]) -> Self {
+        self.highlighted.extend_from_slice(edges);
+        self
+    }
+
+    /// Adds an attribute applied to highlighted edges
+    pub fn highlight_attr(mut self, key: &str, value: &str) -> Self {
+        self.highlight_attrs
+            .push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Places a group of vertices on the same rank
+    pub fn same_rank(mut self, vertices: &[usize]) -> Self {
+        self.ranks.push(vertices.to_vec());
+        self
+    }
+
+    /// Sets the `rankdir` layout hint (e.g. `LR` or `TB`)
+    pub fn rankdir(self, rankdir: &str) -> Self {
+        self.graph_attr("rankdir", rankdir)
+    }
+
+    /// Returns true if an edge is highlighted
+    fn is_highlighted(&self, u: usize, v: usize, directed: bool) -> bool {
+        self.highlighted
+            .iter()
+            .any(|&(a, b)| (a, b) == (u, v) || (!directed && (b, a) == (u, v)))
+    }
+
+    /// Returns the attributes of a single vertex
+    fn vertex_attrs(&self, v: usize) -> Vec<(String, String)> {
+        let mut attrs = Vec::new();
+        if let Some(label) = self.labels.get(v) {
+            attrs.push(("label".to_string(), label.clone()));
+        }
+        if let Some((x, y)) = self.positions.get(v) {
+            let (x, y) = (x * self.scale, y * self.scale);
+            attrs.push(("pos".to_string(), format!("{:.3},{:.3}!", x, y)));
+        }
+        if let Some(colour) = self.colours.get(v) {
+            attrs.push(("style".to_string(), "filled".to_string()));
+            attrs.push(("fillcolor".to_string(), colour.clone()));
+        }
+        attrs
+    }
+}
+
+/// Writes a graph in the DOT format
+pub(crate) fn write_dot<G: GraphConversion + ?Sized>(
+    graph: &G,
+    out: &mut dyn fmt::Write,
+    options: &DotOptions,
+) -> fmt::Result {
+    let directed = graph.is_directed();
+
+    // include graph type and name
+    out.write_str(if directed { "digraph " } else { "graph " })?;
+    if let Some(name) = &options.name {
+        write!(out, "{} ", quote_id(name))?;
+    }
+    out.write_char('{')?;
+
+    // include graph, node and edge defaults
+    for (key, value) in &options.graph_attrs {
+        write!(out, "\n{}=\"{}\";", quote_id(key), escape(value))?;
+    }
+    if !options.node_attrs.is_empty() {
+        write!(out, "\nnode [{}];", attr_list(&options.node_attrs))?;
+    }
+    if !options.edge_attrs.is_empty() {
+        write!(out, "\nedge [{}];", attr_list(&options.edge_attrs))?;
+    }
+
+    // include vertex attributes
+    for v in 0..graph.size() {
+        let attrs = options.vertex_attrs(v);
+        if !attrs.is_empty() {
+            write!(out, "\n{} [{}];", v, attr_list(&attrs))?;
+        }
+    }
+
+    // include rank constraints
+    for rank in &options.ranks {
+        out.write_str("\n{rank=same;")?;
+        for v in rank {
+            write!(out, " {};", v)?;
+        }
+        out.write_char('}')?;
+    }
+
+    // include edges
+    let link = if directed { "->" } else { "--" };
+    let default_highlight = [
+        ("color".to_string(), "red".to_string()),
+        ("penwidth".to_string(), "2".to_string()),
+    ];
+    let highlight_attrs = if options.highlight_attrs.is_empty() {
+        &default_highlight[..]
+    } else {
+        &options.highlight_attrs[..]
+    };
+    for (u, v) in graph.edges() {
+        if options.is_highlighted(u, v, directed) {
+            write!(
+                out,
+                "\n{} {} {} [{}];",
+                u,
+                link,
+                v,
+                attr_list(highlight_attrs)
+            )?;
+        } else {
+            write!(out, "\n{} {} {};", u, link, v)?;
+        }
+    }
+
+    // close graph
+    out.write_str("\n}")
+}
+
+/// Returns many graphs as one DOT document with a cluster per graph
+///
+/// Every graph becomes a `subgraph cluster_k` labelled `graph_k`, and its
+/// vertices are renamed `g{k}_{v}` (keeping `v` as the displayed label) so
+/// that vertices of different graphs never collide. If any graph is
+/// directed the container is a `digraph` and undirected edges are drawn
+/// with `dir=none`.
+///
+/// # Example
+/// ```
+/// use graph6_rs::{to_dot_clusters, Graph};
+/// let graphs = vec![Graph::from_g6("A_").unwrap(), Graph::from_g6("Bw").unwrap()];
+/// let dot = to_dot_clusters(&graphs);
+/// assert!(dot.starts_with("graph {\nsubgraph cluster_0 {"));
+/// assert!(dot.contains("\ng1_1 -- g1_2;"));
+/// ```
+pub fn to_dot_clusters<I>(graphs: I) -> String
+where
+    I: IntoIterator,
+    I::Item: GraphConversion,
+{
+    render(|out| write_dot_clusters(out, graphs))
+}
+
+/// Writes many graphs as one DOT document with a cluster per graph
+///
+/// See [`to_dot_clusters`] for the layout of the document.
+pub fn write_dot_clusters<I>(out: &mut dyn fmt::Write, graphs: I) -> fmt::Result
+where
+    I: IntoIterator,
+    I::Item: GraphConversion,
+{
+    // the container type depends on every graph, so they are collected first
+    let graphs: Vec<I::Item> = graphs.into_iter().collect();
+    let directed = graphs.iter().any(|graph| graph.is_directed());
+
+    out.write_str(if directed { "digraph {" } else { "graph {" })?;
+    for (k, graph) in graphs.into_iter().enumerate() {
+        write!(out, "\nsubgraph cluster_{} {{", k)?;
+        write!(out, "\nlabel=\"graph_{}\";", k)?;
+        for v in 0..graph.size() {
+            write!(out, "\ng{}_{} [label=\"{}\"];", k, v, v)?;
+        }
+        let (link, attrs) = match (directed, graph.is_directed()) {
+            (true, true) => ("->", ""),
+            (true, false) => ("->", " [dir=none]"),
+            (false, _) => ("--", ""),
+        };
+        for (u, v) in graph.edges() {
+            write!(out, "\ng{}_{} {} g{}_{}{};", k, u, link, k, v, attrs)?;
+        }
+        out.write_str("\n}")?;
+    }
+    out.write_str("\n}")
+}
+
+/// Returns many graphs as consecutive DOT documents named `graph_k`
+///
+/// Graphviz renders each document of such a file separately, so directed
+/// and undirected graphs keep their own graph type.
+pub fn to_dot_documents<I>(graphs: I) -> String
+where
+    I: IntoIterator,
+    I::Item: GraphConversion,
+{
+    render(|out| write_dot_documents(out, graphs))
+}
+
+/// Writes many graphs as consecutive DOT documents named `graph_k`
+///
+/// Nothing is buffered between documents, so graphs decoded lazily from a
+/// file are never all held in memory at once.
+pub fn write_dot_documents<I>(out: &mut dyn fmt::Write, graphs: I) -> fmt::Result
+where
+    I: IntoIterator,
+    I::Item: GraphConversion,
+{
+    for (k, graph) in graphs.into_iter().enumerate() {
+        graph.write_dot(out, Some(k))?;
+        out.write_char('\n')?;
+    }
+    Ok(())
+}
+
+/// Formats attributes as a DOT attribute list
+fn attr_list(attrs: &[(String, String)]) -> String {
+    attrs
+        .iter()
+        .map(|(key, value)| format!("{}=\"{}\"", quote_id(key), escape(value)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Formats a graph name or attribute key as a DOT ID, quoting it if needed
+fn quote_id(id: &str) -> String {
+    if is_identifier(id) {
+        id.to_string()
+    } else {
+        format!("\"{}\"", escape(id))
+    }
+}
+
+/// Returns true if the name can be written as an unquoted DOT ID
+fn is_identifier(name: &str) -> bool {
+    const KEYWORDS: [&str; 6] = ["node", "edge", "graph", "digraph", "subgraph", "strict"];
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(name))
+}
+
+/// Escapes backslashes and double quotes inside a quoted DOT string
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod testing {
+    use super::{to_dot_clusters, to_dot_documents, DotOptions};
+    use crate::{DiGraph, Graph, GraphConversion, GraphReader};
+
+    #[test]
+    fn test_default_matches_to_dot() {
+        let graph = Graph::from_g6("C~").unwrap();
+        assert_eq!(graph.to_dot_with(&DotOptions::new()), graph.to_dot(None));
+        let graph = DiGraph::from_d6(r"&C]|w").unwrap();
+        let options = DotOptions::new().id(3);
+        assert_eq!(graph.to_dot_with(&options), graph.to_dot(Some(3)));
+    }
+
+    #[test]
+    fn test_quoted_names() {
+        let graph = Graph::from_g6("@").unwrap();
+        let dot = graph.to_dot_with(&DotOptions::new().name("my graph"));
+        assert_eq!(dot, "graph \"my graph\" {\n}");
+        let dot = graph.to_dot_with(&DotOptions::new().name("Node"));
+        assert_eq!(dot, "graph \"Node\" {\n}");
+        let dot = graph.to_dot_with(&DotOptions::new().name(r#"a\"b"#));
+        assert_eq!(dot, "graph \"a\\\\\\\"b\" {\n}");
+    }
+
+    #[test]
+    fn test_quoted_attribute_keys() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let options = DotOptions::new()
+            .graph_attr("my key", "1")
+            .node_attr("a b", "x")
+            .edge_attr("color", "red")
+            .highlight_edges(&[(0, 1)])
+            .highlight_attr("pen\"width", "2");
+        let dot = graph.to_dot_with(&options);
+        assert_eq!(
+            dot,
+            "graph {\n\"my key\"=\"1\";\nnode [\"a b\"=\"x\"];\nedge [color=\"red\"];\n0 -- 1 [\"pen\\\"width\"=\"2\"];\n}"
+        );
+    }
+
+    #[test]
+    fn test_labels_and_colours() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let options = DotOptions::new()
+            .labels(["a", "b \"quoted\""])
+            .colours(["red"]);
+        let dot = graph.to_dot_with(&options);
+        assert_eq!(
+            dot,
+            "graph {\n0 [label=\"a\", style=\"filled\", fillcolor=\"red\"];\n1 [label=\"b \\\"quoted\\\"\"];\n0 -- 1;\n}"
+        );
+    }
+
+    #[test]
+    fn test_attributes_and_ranks() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let options = DotOptions::new()
+            .rankdir("LR")
+            .node_attr("shape", "point")
+            .edge_attr("color", "gray")
+            .same_rank(&[0, 1]);
+        let dot = graph.to_dot_with(&options);
+        assert_eq!(
+            dot,
+            "graph {\nrankdir=\"LR\";\nnode [shape=\"point\"];\nedge [color=\"gray\"];\n{rank=same; 0; 1;}\n0 -- 1;\n}"
+        );
+    }
+
+    #[test]
+    fn test_highlight_undirected_order() {
+        let graph = Graph::from_g6("Bw").unwrap();
+        let options = DotOptions::new()
+            .highlight_edges(&[(2, 1)])
+            .highlight_attr("style", "dashed");
+        let dot = graph.to_dot_with(&options);
+        assert!(dot.contains("\n1 -- 2 [style=\"dashed\"];"));
+        assert!(dot.contains("\n0 -- 1;"));
+    }
+
+    #[test]
+    fn test_highlight_directed_order() {
+        let graph = DiGraph::from_d6("&AG").unwrap();
+        let dot = graph.to_dot_with(&DotOptions::new().highlight_edges(&[(0, 1)]));
+        assert!(dot.contains("\n1 -> 0;"));
+    }
+
+    #[test]
+    fn test_colouring_palette() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let dot = graph.to_dot_with(&DotOptions::new().colouring(&[0, 10]));
+        assert_eq!(dot.matches("fillcolor=\"#1f77b4\"").count(), 2);
+    }
+
+    #[test]
+    fn test_clusters_undirected() {
+        let graphs = vec![Graph::from_g6("A_").unwrap(), Graph::from_g6("A?").unwrap()];
+        let dot = to_dot_clusters(&graphs);
+        assert_eq!(
+            dot,
+            "graph {\nsubgraph cluster_0 {\nlabel=\"graph_0\";\ng0_0 [label=\"0\"];\ng0_1 [label=\"1\"];\ng0_0 -- g0_1;\n}\nsubgraph cluster_1 {\nlabel=\"graph_1\";\ng1_0 [label=\"0\"];\ng1_1 [label=\"1\"];\n}\n}"
+        );
+    }
+
+    #[test]
+    fn test_clusters_mixed() {
+        let undirected = Graph::from_g6("A_").unwrap();
+        let directed = DiGraph::from_d6("&AG").unwrap();
+        let graphs: Vec<&dyn GraphConversion> = vec![&undirected, &directed];
+        let dot = to_dot_clusters(graphs);
+        assert!(dot.starts_with("digraph {"));
+        assert!(dot.contains("\ng0_0 -> g0_1 [dir=none];"));
+        assert!(dot.contains("\ng1_1 -> g1_0;"));
+    }
+
+    #[test]
+    fn test_documents() {
+        let undirected = Graph::from_g6("A_").unwrap();
+        let directed = DiGraph::from_d6("&AG").unwrap();
+        let graphs: Vec<&dyn GraphConversion> = vec![&undirected, &directed];
+        let dot = to_dot_documents(graphs);
+        assert_eq!(
+            dot,
+            "graph graph_0 {\n0 -- 1;\n}\ndigraph graph_1 {\n1 -> 0;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_documents_from_reader() {
+        let reader = GraphReader::new(&b"A_\nA?\n"[..]);
+        let dot = to_dot_documents(reader.graphs::<Graph>().filter_map(Result::ok));
+        assert_eq!(dot, "graph graph_0 {\n0 -- 1;\n}\ngraph graph_1 {\n}\n");
+    }
+}
@@ -1,4 +1,6 @@
-#[derive(Debug, PartialEq, Eq)]
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum IOError {
     InvalidDigraphHeader,
     InvalidSizeChar,
@@ -7,6 +9,39 @@ pub enum IOError {
     NonCanonicalEncoding,
 }
 
+/// Errors raised when a graph cannot be written in a requested format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConversionError {
+    /// The format is undirected but the digraph's adjacency matrix is not symmetric
+    NonSymmetricAdjacency,
+    /// The format requires all graphs to have the same number of vertices
+    MismatchedGraphSizes,
+    /// The output rejected the write
+    Fmt(fmt::Error),
+}
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonSymmetricAdjacency => f.write_str("adjacency matrix is not symmetric"),
+            Self::MismatchedGraphSizes => f.write_str("graphs have different numbers of vertices"),
+            Self::Fmt(_) => f.write_str("writing the converted graph failed"),
+        }
+    }
+}
+impl std::error::Error for ConversionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Fmt(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+impl From<fmt::Error> for ConversionError {
+    fn from(err: fmt::Error) -> Self {
+        Self::Fmt(err)
+    }
+}
+
 #[cfg(test)]
 mod testing {
     use super::IOError;
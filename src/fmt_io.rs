@@ -0,0 +1,117 @@
+use std::{error::Error, fmt, io};
+
+/// Adapts an [`io::Write`] output for the `write_*` conversions
+///
+/// The text conversions of [`GraphConversion`](crate::GraphConversion)
+/// write into a [`fmt::Write`]. This adapter forwards their output to a
+/// file, socket or any other byte sink. As [`fmt::Error`] carries no
+/// details, the underlying I/O error is kept and returned by
+/// [`FmtWriter::finish`].
+///
+/// # Example
+/// ```
+/// use graph6_rs::{FmtWriter, Graph, GraphConversion};
+/// let graph = Graph::from_g6("A_").unwrap();
+/// let mut out = FmtWriter::new(Vec::new());
+/// let result = graph.write_adjmat(&mut out);
+/// let bytes = out.finish(result).unwrap();
+/// assert_eq!(bytes, b"0 1\n1 0\n");
+/// ```
+#[derive(Debug)]
+pub struct FmtWriter<W> {
+    inner: W,
+    error: Option<io::Error>,
+}
+impl<W: io::Write> FmtWriter<W> {
+    /// Creates a new adapter over an output
+    pub fn new(inner: W) -> Self {
+        Self { inner, error: None }
+    }
+
+    /// Turns the result of a conversion into an I/O result
+    ///
+    /// Returns the underlying output on success, or the I/O error which
+    /// interrupted the conversion. Any other conversion error, such as a
+    /// [`ConversionError`](crate::ConversionError), is wrapped in an
+    /// [`io::ErrorKind::InvalidInput`] error.
+    pub fn finish<E>(self, result: Result<(), E>) -> io::Result<W>
+    where
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        match (result, self.error) {
+            (Ok(()), _) => Ok(self.inner),
+            (Err(_), Some(err)) => Err(err),
+            (Err(err), None) => Err(io::Error::new(io::ErrorKind::InvalidInput, err)),
+        }
+    }
+
+    /// Consumes the adapter and returns the underlying output
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+impl<W: io::Write> fmt::Write for FmtWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::FmtWriter;
+    use crate::{ConversionError, DiGraph, Graph, GraphConversion};
+    use std::io::{self, Write};
+
+    /// An output which accepts a fixed number of bytes
+    #[derive(Debug)]
+    struct Limited(usize);
+    impl Write for Limited {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.0 == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero, "output full"));
+            }
+            let len = buf.len().min(self.0);
+            self.0 -= len;
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_write_through() {
+        let graph = Graph::from_g6("Bw").unwrap();
+        let mut out = FmtWriter::new(Vec::new());
+        let result = graph.write_dimacs(&mut out, Default::default());
+        let bytes = out.finish(result).unwrap();
+        assert_eq!(
+            bytes,
+            graph.to_dimacs(Default::default()).unwrap().as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_io_error() {
+        let graph = Graph::from_g6("Bw").unwrap();
+        let mut out = FmtWriter::new(Limited(8));
+        let result = graph.write_gml(&mut out);
+        let err = out.finish(result).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+    }
+
+    #[test]
+    fn test_conversion_error() {
+        let graph = DiGraph::from_d6("&AG").unwrap();
+        let mut out = FmtWriter::new(Vec::new());
+        let result = graph.write_metis(&mut out);
+        let err = out.finish(result).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        let cause = err.get_ref().unwrap().downcast_ref::<ConversionError>();
+        assert_eq!(cause, Some(&ConversionError::NonSymmetricAdjacency));
+    }
+}
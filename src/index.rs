@@ -0,0 +1,496 @@
+use crate::{read::header_len, Compression, ReadError, ReadGraph};
+use std::{
+    fs::{self, File},
+    io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+/// Magic bytes identifying an index sidecar file
+const MAGIC: &[u8; 8] = b"G6INDEX2";
+
+/// Upper bound on the offsets preallocated when loading a sidecar
+const MAX_PREALLOCATED: u64 = 1 << 16;
+
+/// Byte offsets of every graph in a graph6/digraph6 file
+///
+/// The index allows the k-th graph of a file to be fetched with a single
+/// seek instead of scanning every preceding line.
+///
+/// # Example
+/// ```
+/// use graph6_rs::GraphIndex;
+/// use std::io::Cursor;
+/// let mut file = Cursor::new("A_\nBw\nC~\n");
+/// let index = GraphIndex::build(&mut file).unwrap();
+/// assert_eq!(index.len(), 3);
+/// assert_eq!(index.fetch(&mut file, 1).unwrap().unwrap(), "Bw");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphIndex {
+    offsets: Vec<u64>,
+    file_len: u64,
+    /// Modification time of the indexed file in nanoseconds since the Unix
+    /// epoch, or 0 if unknown
+    modified: u64,
+}
+impl GraphIndex {
+    /// Builds an index by scanning every line of the input
+    ///
+    /// Empty lines and an optional header are skipped, matching the
+    /// behaviour of [`GraphReader`](crate::GraphReader).
+    pub fn build<R: Read>(reader: R) -> io::Result<Self> {
+        let mut reader = BufReader::new(reader);
+        let mut offsets = Vec::new();
+        let mut buf = Vec::new();
+        let mut pos = 0;
+        let mut first = true;
+        loop {
+            buf.clear();
+            let len = reader.read_until(b'\n', &mut buf)?;
+            if len == 0 {
+                break;
+            }
+            let end = trimmed_len(&buf);
+            let start = if first { header_len(&buf[..end]) } else { 0 };
+            if start < end {
+                offsets.push(pos + start as u64);
+            }
+            pos += len as u64;
+            first = false;
+        }
+        Ok(Self {
+            offsets,
+            file_len: pos,
+            modified: 0,
+        })
+    }
+
+    /// Returns the index for a graph file
+    ///
+    /// The sidecar file (see [`GraphIndex::sidecar_path`]) is loaded if it
+    /// exists and matches the length and modification time of the file.
+    /// Otherwise the index is rebuilt and the sidecar is written if
+    /// possible; a sidecar which cannot be written, e.g. next to a file in
+    /// a read-only location, is not an error.
+    ///
+    /// # Errors
+    /// Random access needs an uncompressed file, so gzip and zstd
+    /// compressed files fail with [`io::ErrorKind::Unsupported`].
+    pub fn for_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        ensure_uncompressed(path)?;
+        let sidecar = Self::sidecar_path(path);
+        let metadata = fs::metadata(path)?;
+        let modified = modified_nanos(&metadata);
+        if let Ok(index) = Self::load(&sidecar) {
+            if index.file_len == metadata.len() && index.modified == modified {
+                return Ok(index);
+            }
+        }
+        let mut index = Self::build(File::open(path)?)?;
+        index.modified = modified;
+        // without a sidecar the index is simply rebuilt next time
+        let _ = index.save(&sidecar);
+        Ok(index)
+    }
+
+    /// Returns the path of the sidecar index for a graph file (`<path>.idx`)
+    pub fn sidecar_path<P: AsRef<Path>>(path: P) -> PathBuf {
+        let mut sidecar = path.as_ref().as_os_str().to_owned();
+        sidecar.push(".idx");
+        PathBuf::from(sidecar)
+    }
+
+    /// Writes the index to a sidecar file
+    ///
+    /// Offsets are stored as variable length deltas so the sidecar is
+    /// typically around one byte per graph.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(MAGIC)?;
+        writer.write_all(&self.file_len.to_le_bytes())?;
+        writer.write_all(&self.modified.to_le_bytes())?;
+        writer.write_all(&(self.offsets.len() as u64).to_le_bytes())?;
+        let mut prev = 0;
+        for &offset in &self.offsets {
+            write_varint(&mut writer, offset - prev)?;
+            prev = offset;
+        }
+        writer.flush()
+    }
+
+    /// Reads an index from a sidecar file
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("invalid graph index header"));
+        }
+        let file_len = read_u64(&mut reader)?;
+        let modified = read_u64(&mut reader)?;
+        let count = read_u64(&mut reader)?;
+        if count > file_len {
+            return Err(invalid_data("invalid graph index count"));
+        }
+        let mut offsets = Vec::with_capacity(count.min(MAX_PREALLOCATED) as usize);
+        let mut prev = 0u64;
+        for _ in 0..count {
+            prev = prev
+                .checked_add(read_varint(&mut reader)?)
+                .filter(|&offset| offset < file_len)
+                .ok_or_else(|| invalid_data("invalid graph index offset"))?;
+            offsets.push(prev);
+        }
+        Ok(Self {
+            offsets,
+            file_len,
+            modified,
+        })
+    }
+
+    /// Returns the number of indexed graphs
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Returns true if no graphs were indexed
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Returns the byte offset of the k-th graph
+    pub fn offset(&self, k: usize) -> Option<u64> {
+        self.offsets.get(k).copied()
+    }
+
+    /// Fetches the encoded k-th (0-based) graph by seeking into the input
+    ///
+    /// Returns `Ok(None)` if `k` is out of range.
+    pub fn fetch<R: Read + Seek>(&self, reader: &mut R, k: usize) -> io::Result<Option<String>> {
+        let Some(offset) = self.offset(k) else {
+            return Ok(None);
+        };
+        reader.seek(SeekFrom::Start(offset))?;
+        let mut line = String::new();
+        BufReader::new(reader).read_line(&mut line)?;
+        line.truncate(line.trim_end_matches(['\n', '\r']).len());
+        Ok(Some(line))
+    }
+
+    /// Fetches and decodes the k-th (0-based) graph by seeking into the input
+    ///
+    /// Parse errors report the 1-based position of the graph as their line.
+    pub fn fetch_graph<R, G>(&self, reader: &mut R, k: usize) -> Result<Option<G>, ReadError>
+    where
+        R: Read + Seek,
+        G: ReadGraph,
+    {
+        let Some(repr) = self.fetch(reader, k)? else {
+            return Ok(None);
+        };
+        G::read_graph(&repr)
+            .map(Some)
+            .map_err(|error| ReadError::Parse { line: k + 1, error })
+    }
+
+    /// Returns the encoded k-th graph from an in-memory copy of the file
+    #[cfg(feature = "mmap")]
+    fn line<'a>(&self, bytes: &'a [u8], k: usize) -> Option<&'a [u8]> {
+        let start = self.offset(k)? as usize;
+        let rest = bytes.get(start..)?;
+        let end = rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len());
+        Some(&rest[..trimmed_len(&rest[..end])])
+    }
+}
+
+/// Read-only memory-mapped access to an indexed graph file
+///
+/// # Example
+/// ```no_run
+/// use graph6_rs::{Graph, MmapGraphs};
+/// let graphs = MmapGraphs::open("graphs.g6").unwrap();
+/// let graph: Graph = graphs.get_graph(8_312_004).unwrap().unwrap();
+/// ```
+#[cfg(feature = "mmap")]
+#[derive(Debug)]
+pub struct MmapGraphs {
+    mmap: memmap2::Mmap,
+    index: GraphIndex,
+}
+#[cfg(feature = "mmap")]
+impl MmapGraphs {
+    /// Memory-maps a graph file and loads (or builds) its sidecar index
+    ///
+    /// The file must be uncompressed, see [`GraphIndex::for_file`]. If the
+    /// file changes between indexing and mapping, the index is rebuilt from
+    /// the mapped bytes.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(&path)?;
+        // Safety: the map is read-only; concurrent modification of the file
+        // while it is mapped is undefined behaviour.
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+        let mut index = GraphIndex::for_file(&path)?;
+        if index.file_len != mmap.len() as u64 {
+            index = GraphIndex::build(&mmap[..])?;
+        }
+        Ok(Self { mmap, index })
+    }
+
+    /// Returns the index of the mapped file
+    pub fn index(&self) -> &GraphIndex {
+        &self.index
+    }
+
+    /// Returns the number of graphs in the file
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns true if the file contains no graphs
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Returns the encoded k-th (0-based) graph
+    pub fn get(&self, k: usize) -> Option<&str> {
+        let line = self.index.line(&self.mmap, k)?;
+        std::str::from_utf8(line).ok()
+    }
+
+    /// Returns the decoded k-th (0-based) graph
+    pub fn get_graph<G: ReadGraph>(&self, k: usize) -> Option<Result<G, ReadError>> {
+        let repr = self.get(k)?;
+        Some(G::read_graph(repr).map_err(|error| ReadError::Parse { line: k + 1, error }))
+    }
+}
+
+/// Fails if a file starts with the magic bytes of a compressed stream
+fn ensure_uncompressed(path: &Path) -> io::Result<()> {
+    let mut magic = Vec::with_capacity(4);
+    File::open(path)?.take(4).read_to_end(&mut magic)?;
+    match Compression::from_magic(&magic) {
+        Compression::None => Ok(()),
+        compression => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!(
+                "random access requires an uncompressed file, found {:?}",
+                compression
+            ),
+        )),
+    }
+}
+
+/// Returns the length of a line without its line terminator
+fn trimmed_len(line: &[u8]) -> usize {
+    let mut end = line.len();
+    while end > 0 && matches!(line[end - 1], b'\n' | b'\r') {
+        end -= 1;
+    }
+    end
+}
+
+/// Returns the modification time of a file in nanoseconds, or 0 if unknown
+fn modified_nanos(metadata: &fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |since| since.as_nanos() as u64)
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn write_varint<W: Write>(writer: &mut W, mut value: u64) -> io::Result<()> {
+    while value >= 0x80 {
+        writer.write_all(&[(value as u8 & 0x7f) | 0x80])?;
+        value >>= 7;
+    }
+    writer.write_all(&[value as u8])
+}
+
+fn read_varint<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0];
+        reader.read_exact(&mut byte)?;
+        value |= u64::from(byte[0] & 0x7f) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(invalid_data("invalid graph index offset"))
+}
+
+#[cfg(test)]
+mod testing {
+    use super::GraphIndex;
+    use crate::Graph;
+    use std::{
+        io::Cursor,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("graph6-rs-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_build() {
+        let index = GraphIndex::build(Cursor::new("A_\n\nBw\r\nC~")).unwrap();
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.offset(0), Some(0));
+        assert_eq!(index.offset(1), Some(4));
+        assert_eq!(index.offset(2), Some(8));
+        assert_eq!(index.offset(3), None);
+    }
+
+    #[test]
+    fn test_build_header() {
+        let index = GraphIndex::build(Cursor::new(">>graph6<<A_\nBw\n")).unwrap();
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.offset(0), Some(10));
+    }
+
+    #[test]
+    fn test_fetch() {
+        let mut file = Cursor::new(">>graph6<<A_\nBw\r\nC~");
+        let index = GraphIndex::build(&mut file).unwrap();
+        assert_eq!(index.fetch(&mut file, 2).unwrap().unwrap(), "C~");
+        assert_eq!(index.fetch(&mut file, 0).unwrap().unwrap(), "A_");
+        assert_eq!(index.fetch(&mut file, 1).unwrap().unwrap(), "Bw");
+        assert!(index.fetch(&mut file, 3).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_fetch_graph() {
+        let mut file = Cursor::new("A_\nBw\n");
+        let index = GraphIndex::build(&mut file).unwrap();
+        let graph: Graph = index.fetch_graph(&mut file, 1).unwrap().unwrap();
+        assert_eq!(graph.n, 3);
+    }
+
+    #[test]
+    fn test_save_load() {
+        let lines: String = (0..300)
+            .map(|i| format!("{}\n", "A_".repeat(i % 90 + 1)))
+            .collect();
+        let index = GraphIndex::build(Cursor::new(lines)).unwrap();
+        let path = temp_path("save_load.idx");
+        index.save(&path).unwrap();
+        let loaded = GraphIndex::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(index, loaded);
+    }
+
+    #[test]
+    fn test_for_file() {
+        let path = temp_path("for_file.g6");
+        let sidecar = GraphIndex::sidecar_path(&path);
+        std::fs::write(&path, "A_\nBw\n").unwrap();
+        let index = GraphIndex::for_file(&path).unwrap();
+        assert!(sidecar.exists());
+        assert_eq!(index.len(), 2);
+
+        // a modified file invalidates the sidecar
+        std::fs::write(&path, "A_\nBw\nC~\n").unwrap();
+        let index = GraphIndex::for_file(&path).unwrap();
+        assert_eq!(index.len(), 3);
+
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&sidecar).unwrap();
+    }
+
+    #[test]
+    fn test_for_file_same_length_rewrite() {
+        let path = temp_path("rewrite.g6");
+        let sidecar = GraphIndex::sidecar_path(&path);
+        let set_modified = |secs| {
+            let file = std::fs::File::options().write(true).open(&path).unwrap();
+            file.set_modified(UNIX_EPOCH + Duration::from_secs(secs))
+                .unwrap();
+        };
+        std::fs::write(&path, "A_\nBw\n").unwrap();
+        set_modified(1);
+        assert_eq!(GraphIndex::for_file(&path).unwrap().offset(1), Some(3));
+
+        std::fs::write(&path, "\nA_\nB\n").unwrap();
+        set_modified(2);
+        assert_eq!(GraphIndex::for_file(&path).unwrap().offset(1), Some(4));
+
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&sidecar).unwrap();
+    }
+
+    #[test]
+    fn test_for_file_corrupt_sidecar() {
+        let path = temp_path("corrupt.g6");
+        let sidecar = GraphIndex::sidecar_path(&path);
+        std::fs::write(&path, "A_\nBw\n").unwrap();
+        let mut bytes = super::MAGIC.to_vec();
+        bytes.extend_from_slice(&u64::MAX.to_le_bytes());
+        bytes.extend_from_slice(&0u64.to_le_bytes());
+        bytes.extend_from_slice(&u64::MAX.to_le_bytes());
+        std::fs::write(&sidecar, &bytes).unwrap();
+        assert!(GraphIndex::load(&sidecar).is_err());
+
+        // offsets overflowing or beyond the end of the file are rejected
+        let index = GraphIndex::build(Cursor::new("A_\nBw\n")).unwrap();
+        index.save(&sidecar).unwrap();
+        let mut bytes = std::fs::read(&sidecar).unwrap();
+        *bytes.last_mut().unwrap() = 6;
+        std::fs::write(&sidecar, &bytes).unwrap();
+        assert!(GraphIndex::load(&sidecar).is_err());
+
+        assert_eq!(GraphIndex::for_file(&path).unwrap().len(), 2);
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&sidecar).unwrap();
+    }
+
+    #[test]
+    fn test_for_file_unwritable_sidecar() {
+        let path = temp_path("unwritable.g6");
+        let sidecar = GraphIndex::sidecar_path(&path);
+        std::fs::write(&path, "A_\nBw\n").unwrap();
+        // a directory in place of the sidecar can be neither read nor written
+        std::fs::create_dir_all(&sidecar).unwrap();
+        assert_eq!(GraphIndex::for_file(&path).unwrap().len(), 2);
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_dir(&sidecar).unwrap();
+    }
+
+    #[test]
+    fn test_for_file_compressed() {
+        let path = temp_path("compressed.g6.gz");
+        std::fs::write(&path, [0x1f, 0x8b, 0x08, 0x00]).unwrap();
+        let err = GraphIndex::for_file(&path).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+        assert!(!GraphIndex::sidecar_path(&path).exists());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_mmap() {
+        let path = temp_path("mmap.g6");
+        std::fs::write(&path, "A_\nBw\r\nC~").unwrap();
+        let graphs = super::MmapGraphs::open(&path).unwrap();
+        assert_eq!(graphs.len(), 3);
+        assert_eq!(graphs.get(1), Some("Bw"));
+        assert_eq!(graphs.get(2), Some("C~"));
+        assert_eq!(graphs.get(3), None);
+        let graph: Graph = graphs.get_graph(0).unwrap().unwrap();
+        assert_eq!(graph.n, 2);
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(GraphIndex::sidecar_path(&path)).unwrap();
+    }
+}
@@ -0,0 +1,122 @@
+use crate::GraphConversion;
+use serde::Serialize;
+use std::io;
+
+/// Layouts available for JSON exports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JsonLayout {
+    /// Node-link layout compatible with networkx's `node_link_data` (and D3)
+    #[default]
+    NodeLink,
+    /// Cytoscape.js `elements` layout
+    Cytoscape,
+}
+
+#[derive(Serialize)]
+struct NodeLink {
+    directed: bool,
+    multigraph: bool,
+    graph: Empty,
+    nodes: Vec<NodeLinkNode>,
+    links: Vec<NodeLinkLink>,
+}
+
+#[derive(Serialize)]
+struct Empty {}
+
+#[derive(Serialize)]
+struct NodeLinkNode {
+    id: usize,
+}
+
+#[derive(Serialize)]
+struct NodeLinkLink {
+    source: usize,
+    target: usize,
+}
+
+#[derive(Serialize)]
+struct Cytoscape {
+    directed: bool,
+    elements: CytoscapeElements,
+}
+
+#[derive(Serialize)]
+struct CytoscapeElements {
+    nodes: Vec<CytoscapeElement<CytoscapeNode>>,
+    edges: Vec<CytoscapeElement<CytoscapeEdge>>,
+}
+
+#[derive(Serialize)]
+struct CytoscapeElement<T> {
+    data: T,
+}
+
+#[derive(Serialize)]
+struct CytoscapeNode {
+    id: String,
+}
+
+#[derive(Serialize)]
+struct CytoscapeEdge {
+    id: String,
+    source: String,
+    target: String,
+}
+
+/// Serializes a graph into one of the JSON layouts
+pub(crate) fn to_json<G: GraphConversion + ?Sized>(graph: &G, layout: JsonLayout) -> String {
+    let mut json = Vec::new();
+    // serialization of these plain structs cannot fail
+    write_json(graph, &mut json, layout).expect("graph serialization failed");
+    String::from_utf8(json).expect("serde_json writes valid UTF-8")
+}
+
+/// Serializes a graph into one of the JSON layouts, writing to `out`
+pub(crate) fn write_json<G: GraphConversion + ?Sized>(
+    graph: &G,
+    out: &mut dyn io::Write,
+    layout: JsonLayout,
+) -> io::Result<()> {
+    let result = match layout {
+        JsonLayout::NodeLink => serde_json::to_writer(
+            &mut *out,
+            &NodeLink {
+                directed: graph.is_directed(),
+                multigraph: false,
+                graph: Empty {},
+                nodes: (0..graph.size()).map(|id| NodeLinkNode { id }).collect(),
+                links: graph
+                    .edges()
+                    .into_iter()
+                    .map(|(source, target)| NodeLinkLink { source, target })
+                    .collect(),
+            },
+        ),
+        JsonLayout::Cytoscape => serde_json::to_writer(
+            &mut *out,
+            &Cytoscape {
+                directed: graph.is_directed(),
+                elements: CytoscapeElements {
+                    nodes: (0..graph.size())
+                        .map(|id| CytoscapeElement {
+                            data: CytoscapeNode { id: id.to_string() },
+                        })
+                        .collect(),
+                    edges: graph
+                        .edges()
+                        .into_iter()
+                        .map(|(u, v)| CytoscapeElement {
+                            data: CytoscapeEdge {
+                                id: format!("e{}-{}", u, v),
+                                source: u.to_string(),
+                                target: v.to_string(),
+                            },
+                        })
+                        .collect(),
+                },
+            },
+        ),
+    };
+    result.map_err(io::Error::from)
+}
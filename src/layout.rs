@@ -0,0 +1,393 @@
+use crate::{utils::SplitMix64, GraphConversion};
+use std::{collections::VecDeque, f64::consts::PI};
+
+/// Vertex layouts used to place vertices in drawings
+///
+/// Coordinates are returned in the `[-1, 1]` square with `y` pointing up,
+/// independently of any renderer.
+///
+/// # Example
+/// ```
+/// use graph6_rs::{Graph, GraphConversion, Layout};
+/// let graph = Graph::from_g6("Bw").unwrap();
+/// let positions = Layout::Spring { iterations: 50, seed: 42 }.positions(&graph);
+/// assert_eq!(positions.len(), 3);
+/// let dot = graph.to_dot_positioned(None, &positions);
+/// assert!(dot.contains("0 [pos="));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Layout {
+    /// Vertices evenly spaced on the unit circle
+    #[default]
+    Circular,
+    /// Fruchterman-Reingold force-directed placement from a seeded random
+    /// start, scaled into the unit square
+    Spring { iterations: usize, seed: u64 },
+    /// Two columns from a breadth-first 2-colouring of the graph
+    Bipartite,
+    /// Concentric circles, one per group of vertices (innermost first)
+    ///
+    /// Vertices missing from every shell are placed on an extra outer shell.
+    Shell(Vec<Vec<usize>>),
+    /// Eigenvectors of the second and third smallest eigenvalues of the
+    /// graph Laplacian (arcs are treated as undirected edges)
+    Spectral,
+}
+impl Layout {
+    /// Returns the coordinates of every vertex of a graph
+    pub fn positions<G: GraphConversion + ?Sized>(&self, graph: &G) -> Vec<(f64, f64)> {
+        match self {
+            Self::Circular => circular(graph.size()),
+            Self::Spring { iterations, seed } => spring(graph, *iterations, *seed),
+            Self::Bipartite => bipartite(graph),
+            Self::Shell(shells) => shell(graph.size(), shells),
+            Self::Spectral => spectral(graph),
+        }
+    }
+}
+
+/// Places `n` vertices evenly on the unit circle, starting at angle zero
+fn circular(n: usize) -> Vec<(f64, f64)> {
+    if n == 1 {
+        return vec![(0.0, 0.0)];
+    }
+    (0..n)
+        .map(|i| {
+            let theta = 2.0 * PI * i as f64 / n as f64;
+            (theta.cos(), theta.sin())
+        })
+        .collect()
+}
+
+/// Fruchterman-Reingold force-directed layout
+///
+/// Arcs of directed graphs are treated as undirected edges.
+fn spring<G: GraphConversion + ?Sized>(graph: &G, iterations: usize, seed: u64) -> Vec<(f64, f64)> {
+    let n = graph.size();
+    if n < 2 {
+        return vec![(0.0, 0.0); n];
+    }
+    let mut rng = SplitMix64::new(seed);
+    let mut unit = || rng.next_u64() as f64 / u64::MAX as f64 * 2.0 - 1.0;
+    let mut pos: Vec<(f64, f64)> = (0..n).map(|_| (unit(), unit())).collect();
+    let edges: Vec<(usize, usize)> = graph.edges().into_iter().filter(|(u, v)| u != v).collect();
+
+    // optimal distance for vertices spread over the [-1, 1] square
+    let k = (4.0 / n as f64).sqrt();
+    let mut temperature = 0.1;
+    let cooling = temperature / (iterations as f64 + 1.0);
+    for _ in 0..iterations {
+        let mut disp = vec![(0.0, 0.0); n];
+        for i in 0..n {
+            for j in i + 1..n {
+                let (dx, dy) = (pos[i].0 - pos[j].0, pos[i].1 - pos[j].1);
+                let dist = (dx * dx + dy * dy).sqrt().max(1e-9);
+                let force = k * k / dist;
+                disp[i].0 += dx / dist * force;
+                disp[i].1 += dy / dist * force;
+                disp[j].0 -= dx / dist * force;
+                disp[j].1 -= dy / dist * force;
+            }
+        }
+        for &(u, v) in &edges {
+            let (dx, dy) = (pos[u].0 - pos[v].0, pos[u].1 - pos[v].1);
+            let dist = (dx * dx + dy * dy).sqrt().max(1e-9);
+            let force = dist * dist / k;
+            disp[u].0 -= dx / dist * force;
+            disp[u].1 -= dy / dist * force;
+            disp[v].0 += dx / dist * force;
+            disp[v].1 += dy / dist * force;
+        }
+        for (p, d) in pos.iter_mut().zip(&disp) {
+            let len = (d.0 * d.0 + d.1 * d.1).sqrt().max(1e-9);
+            let step = len.min(temperature);
+            p.0 += d.0 / len * step;
+            p.1 += d.1 / len * step;
+        }
+        temperature -= cooling;
+    }
+    rescale(pos)
+}
+
+/// Two column layout from a breadth-first 2-colouring
+///
+/// Vertices of the first colour class are placed on the left, the second
+/// on the right. Graphs which are not bipartite are coloured greedily.
+fn bipartite<G: GraphConversion + ?Sized>(graph: &G) -> Vec<(f64, f64)> {
+    let n = graph.size();
+    let bit_vec = graph.bit_vec();
+    let mut colour = vec![None; n];
+    for start in 0..n {
+        if colour[start].is_some() {
+            continue;
+        }
+        colour[start] = Some(false);
+        let mut queue = VecDeque::from([start]);
+        while let Some(u) = queue.pop_front() {
+            for v in 0..n {
+                let adjacent = bit_vec[u * n + v] == 1 || bit_vec[v * n + u] == 1;
+                if adjacent && colour[v].is_none() {
+                    colour[v] = colour[u].map(|c| !c);
+                    queue.push_back(v);
+                }
+            }
+        }
+    }
+    let sides: Vec<bool> = colour.into_iter().map(|c| c.unwrap_or_default()).collect();
+    let counts = [
+        sides.iter().filter(|&&side| !side).count(),
+        sides.iter().filter(|&&side| side).count(),
+    ];
+    let mut seen = [0, 0];
+    sides
+        .iter()
+        .map(|&side| {
+            let idx = side as usize;
+            let y = if counts[idx] > 1 {
+                1.0 - 2.0 * seen[idx] as f64 / (counts[idx] - 1) as f64
+            } else {
+                0.0
+            };
+            seen[idx] += 1;
+            (if side { 1.0 } else { -1.0 }, y)
+        })
+        .collect()
+}
+
+/// Places groups of vertices on concentric circles of increasing radius
+fn shell(n: usize, shells: &[Vec<usize>]) -> Vec<(f64, f64)> {
+    let mut shells: Vec<Vec<usize>> = shells
+        .iter()
+        .map(|shell| shell.iter().copied().filter(|&v| v < n).collect())
+        .filter(|shell: &Vec<usize>| !shell.is_empty())
+        .collect();
+    let mut placed = vec![false; n];
+    shells.iter().flatten().for_each(|&v| placed[v] = true);
+    let rest: Vec<usize> = (0..n).filter(|&v| !placed[v]).collect();
+    if !rest.is_empty() {
+        shells.push(rest);
+    }
+
+    let mut pos = vec![(0.0, 0.0); n];
+    let bump = 1.0 / shells.len().max(1) as f64;
+    let mut radius = if shells.first().is_some_and(|shell| shell.len() == 1) {
+        0.0
+    } else {
+        bump
+    };
+    for shell in &shells {
+        for (v, (x, y)) in shell.iter().zip(circular(shell.len().max(2))) {
+            pos[*v] = (x * radius, y * radius);
+        }
+        radius += bump;
+    }
+    pos
+}
+
+/// Spectral layout from the eigenvectors of the graph Laplacian
+fn spectral<G: GraphConversion + ?Sized>(graph: &G) -> Vec<(f64, f64)> {
+    let n = graph.size();
+    if n < 2 {
+        return vec![(0.0, 0.0); n];
+    }
+    let bit_vec = graph.bit_vec();
+    let mut laplacian = vec![0.0; n * n];
+    for i in 0..n {
+        for j in 0..n {
+            if i != j && (bit_vec[i * n + j] == 1 || bit_vec[j * n + i] == 1) {
+                laplacian[i * n + j] = -1.0;
+                laplacian[i * n + i] += 1.0;
+            }
+        }
+    }
+    let (values, vectors) = jacobi_eigen(laplacian, n);
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+    let x = order[1];
+    let pos = (0..n)
+        .map(|i| {
+            let y = order.get(2).map_or(0.0, |&y| vectors[i * n + y]);
+            (vectors[i * n + x], y)
+        })
+        .collect();
+    rescale(pos)
+}
+
+/// Eigen decomposition of a symmetric matrix using cyclic Jacobi rotations
+///
+/// Returns the eigenvalues and a row-major matrix whose columns are the
+/// corresponding eigenvectors.
+fn jacobi_eigen(mut a: Vec<f64>, n: usize) -> (Vec<f64>, Vec<f64>) {
+    let mut v = vec![0.0; n * n];
+    (0..n).for_each(|i| v[i * n + i] = 1.0);
+    for _ in 0..100 {
+        let off: f64 = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i * n + j] * a[i * n + j])
+            .sum();
+        if off < 1e-18 {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = a[p * n + q];
+                if apq.abs() < 1e-15 {
+                    continue;
+                }
+                let theta = (a[q * n + q] - a[p * n + p]) / (2.0 * apq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for k in 0..n {
+                    let (akp, akq) = (a[k * n + p], a[k * n + q]);
+                    a[k * n + p] = c * akp - s * akq;
+                    a[k * n + q] = s * akp + c * akq;
+                }
+                for k in 0..n {
+                    let (apk, aqk) = (a[p * n + k], a[q * n + k]);
+                    a[p * n + k] = c * apk - s * aqk;
+                    a[q * n + k] = s * apk + c * aqk;
+                }
+                for k in 0..n {
+                    let (vkp, vkq) = (v[k * n + p], v[k * n + q]);
+                    v[k * n + p] = c * vkp - s * vkq;
+                    v[k * n + q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+    ((0..n).map(|i| a[i * n + i]).collect(), v)
+}
+
+/// Centers positions at the origin and scales them into the unit square
+fn rescale(mut pos: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
+    let n = pos.len() as f64;
+    let (cx, cy) = pos
+        .iter()
+        .fold((0.0, 0.0), |acc, p| (acc.0 + p.0 / n, acc.1 + p.1 / n));
+    let extent = pos
+        .iter()
+        .map(|p| (p.0 - cx).abs().max((p.1 - cy).abs()))
+        .fold(0.0, f64::max);
+    let extent = if extent > 0.0 { extent } else { 1.0 };
+    for p in pos.iter_mut() {
+        *p = ((p.0 - cx) / extent, (p.1 - cy) / extent);
+    }
+    pos
+}
+
+#[cfg(test)]
+mod testing {
+    use super::Layout;
+    use crate::Graph;
+
+    fn assert_close(a: (f64, f64), b: (f64, f64)) {
+        assert!(
+            (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9,
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn test_circular() {
+        let graph = Graph::from_g6("C~").unwrap();
+        let pos = Layout::Circular.positions(&graph);
+        assert_eq!(pos.len(), 4);
+        assert_close(pos[0], (1.0, 0.0));
+        assert_close(pos[1], (0.0, 1.0));
+        assert_close(pos[2], (-1.0, 0.0));
+        assert_close(pos[3], (0.0, -1.0));
+    }
+
+    #[test]
+    fn test_circular_single() {
+        let graph = Graph::from_adj(&[0]).unwrap();
+        assert_eq!(Layout::Circular.positions(&graph), vec![(0.0, 0.0)]);
+    }
+
+    #[test]
+    fn test_spring_seeded() {
+        let graph = Graph::from_g6("C~").unwrap();
+        let layout = Layout::Spring {
+            iterations: 50,
+            seed: 1,
+        };
+        let pos = layout.positions(&graph);
+        assert_eq!(pos, layout.positions(&graph));
+        assert!(pos
+            .iter()
+            .all(|p| p.0.abs() <= 1.0 + 1e-9 && p.1.abs() <= 1.0 + 1e-9));
+    }
+
+    #[test]
+    fn test_spring_separates_vertices() {
+        let graph = Graph::from_g6("C~").unwrap();
+        let pos = Layout::Spring {
+            iterations: 100,
+            seed: 3,
+        }
+        .positions(&graph);
+        for i in 0..4 {
+            for j in i + 1..4 {
+                let d = ((pos[i].0 - pos[j].0).powi(2) + (pos[i].1 - pos[j].1).powi(2)).sqrt();
+                assert!(d > 0.1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bipartite() {
+        // path 0 - 1 - 2
+        let graph = Graph::from_adj(&[0, 1, 0, 1, 0, 1, 0, 1, 0]).unwrap();
+        let pos = Layout::Bipartite.positions(&graph);
+        assert_eq!(pos, vec![(-1.0, 1.0), (1.0, 0.0), (-1.0, -1.0)]);
+    }
+
+    #[test]
+    fn test_shell() {
+        let graph = Graph::from_g6("C~").unwrap();
+        let pos = Layout::Shell(vec![vec![0]]).positions(&graph);
+        assert_close(pos[0], (0.0, 0.0));
+        for p in &pos[1..] {
+            assert!(((p.0 * p.0 + p.1 * p.1).sqrt() - 0.5).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_shell_two_rings() {
+        let graph = Graph::from_g6("C~").unwrap();
+        let pos = Layout::Shell(vec![vec![0, 1], vec![2, 3]]).positions(&graph);
+        assert_close(pos[0], (0.5, 0.0));
+        assert_close(pos[1], (-0.5, 0.0));
+        assert_close(pos[2], (1.0, 0.0));
+        assert_close(pos[3], (-1.0, 0.0));
+    }
+
+    #[test]
+    fn test_spectral_path() {
+        // path 0 - 1 - 2 - 3: the Fiedler vector orders the vertices
+        let adj = [0, 1, 0, 0, 1, 0, 1, 0, 0, 1, 0, 1, 0, 0, 1, 0];
+        let graph = Graph::from_adj(&adj).unwrap();
+        let pos = Layout::Spectral.positions(&graph);
+        let xs: Vec<f64> = pos.iter().map(|p| p.0).collect();
+        let increasing = xs.windows(2).all(|w| w[0] < w[1]);
+        let decreasing = xs.windows(2).all(|w| w[0] > w[1]);
+        assert!(increasing || decreasing, "{:?}", xs);
+    }
+
+    #[test]
+    fn test_jacobi_eigen() {
+        let (values, vectors) = super::jacobi_eigen(vec![2.0, 1.0, 1.0, 2.0], 2);
+        let mut sorted = values.clone();
+        sorted.sort_by(f64::total_cmp);
+        assert!((sorted[0] - 1.0).abs() < 1e-9 && (sorted[1] - 3.0).abs() < 1e-9);
+        // A v = lambda v for every eigenpair
+        for k in 0..2 {
+            let (v0, v1) = (vectors[k], vectors[2 + k]);
+            assert!((2.0 * v0 + v1 - values[k] * v0).abs() < 1e-9);
+            assert!((v0 + 2.0 * v1 - values[k] * v1).abs() < 1e-9);
+        }
+    }
+}
@@ -1,11 +1,47 @@
+#[cfg(feature = "tokio")]
+mod async_io;
+mod compress;
 mod conversion;
 mod directed;
+mod dot;
 mod error;
+mod fmt_io;
+mod index;
+#[cfg(feature = "serde")]
+mod json;
+mod layout;
+mod npy;
+mod options;
+mod read;
+mod stream;
+mod svg;
+mod template;
 mod undirected;
 mod utils;
 mod write;
-pub use conversion::GraphConversion;
+#[cfg(feature = "tokio")]
+pub use async_io::{AsyncGraphReader, AsyncGraphWriter};
+pub use compress::{compress, decompress, Compression, Encoder};
+pub use conversion::{to_graphml_document, write_graphml_document, GraphConversion, NodeAttribute};
 pub use directed::DiGraph;
-pub use error::IOError;
+pub use dot::{
+    to_dot_clusters, to_dot_documents, write_dot_clusters, write_dot_documents, DotOptions,
+};
+pub use error::{ConversionError, IOError};
+pub use fmt_io::FmtWriter;
+pub use index::GraphIndex;
+#[cfg(feature = "mmap")]
+pub use index::MmapGraphs;
+#[cfg(feature = "serde")]
+pub use json::JsonLayout;
+pub use layout::Layout;
+pub use npy::{to_npy_batch, to_npz, write_npy_batch, write_npz, NpyDtype};
+pub use options::{DimacsFormat, EdgeListOptions, ListgFormat, NetOptions, TikzOptions};
+pub use read::{
+    CallbackGraphs, GraphReader, Graphs, LineError, Lines, ReadError, ReadGraph, SkippingGraphs,
+};
+pub use stream::{GraphStream, ResMod};
+pub use svg::{to_svg_grid, write_svg_grid, SvgOptions};
+pub use template::Template;
 pub use undirected::Graph;
-pub use write::{write_graph6, WriteGraph};
+pub use write::{write_graph6, GraphWriter, WriteGraph};
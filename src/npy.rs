@@ -0,0 +1,438 @@
+use crate::{ConversionError, GraphConversion};
+use std::io;
+
+/// Element types available for NumPy exports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NpyDtype {
+    /// Unsigned bytes (`uint8`)
+    #[default]
+    U8,
+    /// Booleans (`bool`)
+    Bool,
+    /// 32-bit floats (`float32`)
+    F32,
+}
+impl NpyDtype {
+    /// Returns the NumPy type descriptor
+    fn descr(&self) -> &'static str {
+        match self {
+            Self::U8 => "|u1",
+            Self::Bool => "|b1",
+            Self::F32 => "<f4",
+        }
+    }
+
+    /// Appends a single adjacency entry to the array data
+    fn push(&self, data: &mut Vec<u8>, bit: usize) {
+        match self {
+            Self::U8 | Self::Bool => data.push(bit as u8),
+            Self::F32 => data.extend_from_slice(&(bit as f32).to_le_bytes()),
+        }
+    }
+}
+
+/// Returns the adjacency matrix of a graph as a 2-D `.npy` file
+pub(crate) fn to_npy<G: GraphConversion + ?Sized>(graph: &G, dtype: NpyDtype) -> Vec<u8> {
+    let mut npy = Vec::new();
+    // writing into a Vec cannot fail
+    write_npy(graph, &mut npy, dtype).expect("conversion into a Vec failed");
+    npy
+}
+
+/// Writes the adjacency matrix of a graph as a 2-D `.npy` file, one row at a time
+pub(crate) fn write_npy<G: GraphConversion + ?Sized>(
+    graph: &G,
+    out: &mut dyn io::Write,
+    dtype: NpyDtype,
+) -> io::Result<()> {
+    let n = graph.size();
+    out.write_all(&npy_header(dtype, &[n, n]))?;
+    let mut row = Vec::new();
+    for bits in graph.bit_vec().chunks(n.max(1)) {
+        row.clear();
+        for &bit in bits {
+            dtype.push(&mut row, bit);
+        }
+        out.write_all(&row)?;
+    }
+    Ok(())
+}
+
+/// Returns the adjacency matrices of same-size graphs as a 3-D `.npy` file
+///
+/// The resulting array has shape `(k, n, n)` for `k` graphs on `n` vertices.
+///
+/// # Errors
+/// Returns an error if the graphs do not all have the same number of vertices
+///
+/// # Example
+/// ```
+/// use graph6_rs::{to_npy_batch, Graph, NpyDtype};
+/// let graphs = vec![Graph::from_g6("A_").unwrap(), Graph::from_g6("A?").unwrap()];
+/// let npy = to_npy_batch(&graphs, NpyDtype::U8).unwrap();
+/// assert!(npy.starts_with(b"\x93NUMPY"));
+/// assert_eq!(&npy[npy.len() - 8..], &[0, 1, 1, 0, 0, 0, 0, 0]);
+/// ```
+pub fn to_npy_batch<I>(graphs: I, dtype: NpyDtype) -> Result<Vec<u8>, ConversionError>
+where
+    I: IntoIterator,
+    I::Item: GraphConversion,
+{
+    let graphs: Vec<I::Item> = graphs.into_iter().collect();
+    let n = common_size(&graphs)?;
+    let mut npy = Vec::new();
+    // writing into a Vec cannot fail
+    write_batch(&mut npy, &graphs, n, dtype).expect("conversion into a Vec failed");
+    Ok(npy)
+}
+
+/// Writes the adjacency matrices of same-size graphs as a 3-D `.npy` file
+///
+/// The array shape is part of the header, so the graphs are collected
+/// before anything is written. See [`to_npy_batch`] for the layout.
+///
+/// # Errors
+/// Returns an [`io::ErrorKind::InvalidInput`] error, without writing
+/// anything, if the graphs do not all have the same number of vertices
+pub fn write_npy_batch<I>(out: &mut dyn io::Write, graphs: I, dtype: NpyDtype) -> io::Result<()>
+where
+    I: IntoIterator,
+    I::Item: GraphConversion,
+{
+    let graphs: Vec<I::Item> = graphs.into_iter().collect();
+    let n = common_size(&graphs).map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    write_batch(out, &graphs, n, dtype)
+}
+
+/// Returns the number of vertices shared by all graphs
+fn common_size<G: GraphConversion>(graphs: &[G]) -> Result<usize, ConversionError> {
+    let n = graphs.first().map_or(0, |graph| graph.size());
+    if graphs.iter().any(|graph| graph.size() != n) {
+        return Err(ConversionError::MismatchedGraphSizes);
+    }
+    Ok(n)
+}
+
+/// Writes the header and matrices of a 3-D `.npy` file
+fn write_batch<G: GraphConversion>(
+    out: &mut dyn io::Write,
+    graphs: &[G],
+    n: usize,
+    dtype: NpyDtype,
+) -> io::Result<()> {
+    out.write_all(&npy_header(dtype, &[graphs.len(), n, n]))?;
+    let mut data = Vec::new();
+    for graph in graphs {
+        data.clear();
+        for &bit in graph.bit_vec() {
+            dtype.push(&mut data, bit);
+        }
+        out.write_all(&data)?;
+    }
+    Ok(())
+}
+
+/// Returns the adjacency matrices of many graphs as an `.npz` archive
+///
+/// Graphs are stored as `arr_0`, `arr_1`, ... matching `numpy.savez`, so
+/// graphs of different sizes can share one archive. The archive is
+/// written uncompressed and without ZIP64 records.
+///
+/// # Errors
+/// Returns an [`io::ErrorKind::InvalidInput`] error if the archive would
+/// hold more than 65,534 graphs or reach 4 GiB
+///
+/// # Example
+/// ```
+/// use graph6_rs::{to_npz, Graph, NpyDtype};
+/// let graphs = vec![Graph::from_g6("A_").unwrap(), Graph::from_g6("Bw").unwrap()];
+/// let npz = to_npz(&graphs, NpyDtype::Bool).unwrap();
+/// assert!(npz.starts_with(b"PK\x03\x04"));
+/// ```
+pub fn to_npz<I>(graphs: I, dtype: NpyDtype) -> io::Result<Vec<u8>>
+where
+    I: IntoIterator,
+    I::Item: GraphConversion,
+{
+    let mut npz = Vec::new();
+    write_npz(&mut npz, graphs, dtype)?;
+    Ok(npz)
+}
+
+/// Writes the adjacency matrices of many graphs as an `.npz` archive
+///
+/// Only one graph's array is held in memory at a time; the archive's
+/// central directory is written after the last graph. See [`to_npz`] for
+/// the layout and size limits; on error the output holds an incomplete
+/// archive.
+pub fn write_npz<I>(out: &mut dyn io::Write, graphs: I, dtype: NpyDtype) -> io::Result<()>
+where
+    I: IntoIterator,
+    I::Item: GraphConversion,
+{
+    let mut zip = ZipWriter::new(out);
+    for (k, graph) in graphs.into_iter().enumerate() {
+        zip.add(&format!("arr_{}.npy", k), &to_npy(&graph, dtype))?;
+    }
+    zip.finish()
+}
+
+/// Builds a version 1.0 `.npy` header padded to a 64 byte boundary
+fn npy_header(dtype: NpyDtype, shape: &[usize]) -> Vec<u8> {
+    let shape = match shape {
+        [len] => format!("({},)", len),
+        _ => format!(
+            "({})",
+            shape
+                .iter()
+                .map(usize::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let mut dict = format!(
+        "{{'descr': '{}', 'fortran_order': False, 'shape': {}, }}",
+        dtype.descr(),
+        shape
+    );
+    // magic (6) + version (2) + header length (2) + dict + newline
+    let unpadded = 10 + dict.len() + 1;
+    dict.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+    dict.push('\n');
+
+    let mut header = Vec::with_capacity(10 + dict.len());
+    header.extend_from_slice(b"\x93NUMPY\x01\x00");
+    header.extend_from_slice(&(dict.len() as u16).to_le_bytes());
+    header.extend_from_slice(dict.as_bytes());
+    header
+}
+
+/// Most entries a zip archive holds without ZIP64 records
+///
+/// `0xffff` itself tells readers to look for a ZIP64 directory, as does
+/// `0xffffffff` for sizes and offsets, so both limits stay below them.
+const MAX_ENTRIES: usize = 0xffff - 1;
+
+/// Converts a size or offset into a 32-bit zip field
+fn zip_u32(value: usize) -> io::Result<u32> {
+    u32::try_from(value)
+        .ok()
+        .filter(|&value| value != u32::MAX)
+        .ok_or_else(|| too_large("sizes or offsets of 4 GiB"))
+}
+
+/// Returns the error for archives which would need ZIP64 records
+fn too_large(what: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("zip archive would need {}, which requires ZIP64", what),
+    )
+}
+
+/// Writes an uncompressed (stored) zip archive entry by entry
+struct ZipWriter<'a> {
+    out: &'a mut dyn io::Write,
+    /// Bytes written so far, i.e. the offset of the next entry
+    offset: usize,
+    /// Central directory records, written by [`ZipWriter::finish`]
+    central: Vec<u8>,
+    entries: usize,
+}
+impl<'a> ZipWriter<'a> {
+    fn new(out: &'a mut dyn io::Write) -> Self {
+        Self {
+            out,
+            offset: 0,
+            central: Vec::new(),
+            entries: 0,
+        }
+    }
+
+    /// Writes a local file header followed by the entry data
+    fn add(&mut self, name: &str, data: &[u8]) -> io::Result<()> {
+        // 1980-01-01 00:00:00 in MS-DOS date/time format
+        const DOS_TIME: u16 = 0;
+        const DOS_DATE: u16 = 0x21;
+
+        if self.entries >= MAX_ENTRIES {
+            return Err(too_large("more than 65,534 entries"));
+        }
+        let offset = zip_u32(self.offset)?;
+        let size = zip_u32(data.len())?;
+
+        let crc = crc32(data);
+        let mut fields = Vec::new();
+        fields.extend_from_slice(&20u16.to_le_bytes()); // version needed
+        fields.extend_from_slice(&0u16.to_le_bytes()); // flags
+        fields.extend_from_slice(&0u16.to_le_bytes()); // stored
+        fields.extend_from_slice(&DOS_TIME.to_le_bytes());
+        fields.extend_from_slice(&DOS_DATE.to_le_bytes());
+        fields.extend_from_slice(&crc.to_le_bytes());
+        fields.extend_from_slice(&size.to_le_bytes());
+        fields.extend_from_slice(&size.to_le_bytes());
+        fields.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        fields.extend_from_slice(&0u16.to_le_bytes()); // extra length
+
+        let mut local = Vec::new();
+        local.extend_from_slice(&0x04034b50u32.to_le_bytes());
+        local.extend_from_slice(&fields);
+        local.extend_from_slice(name.as_bytes());
+        self.out.write_all(&local)?;
+        self.out.write_all(data)?;
+
+        self.central.extend_from_slice(&0x02014b50u32.to_le_bytes());
+        self.central.extend_from_slice(&20u16.to_le_bytes()); // version made by
+        self.central.extend_from_slice(&fields);
+        self.central.extend_from_slice(&0u16.to_le_bytes()); // comment length
+        self.central.extend_from_slice(&0u16.to_le_bytes()); // disk number
+        self.central.extend_from_slice(&0u16.to_le_bytes()); // internal attributes
+        self.central.extend_from_slice(&0u32.to_le_bytes()); // external attributes
+        self.central.extend_from_slice(&offset.to_le_bytes());
+        self.central.extend_from_slice(name.as_bytes());
+
+        self.offset += local.len() + data.len();
+        self.entries += 1;
+        Ok(())
+    }
+
+    /// Writes the central directory and its end record
+    fn finish(self) -> io::Result<()> {
+        let central_size = zip_u32(self.central.len())?;
+        let central_offset = zip_u32(self.offset)?;
+        let mut end = Vec::new();
+        end.extend_from_slice(&0x06054b50u32.to_le_bytes());
+        end.extend_from_slice(&0u16.to_le_bytes()); // disk number
+        end.extend_from_slice(&0u16.to_le_bytes()); // central directory disk
+        end.extend_from_slice(&(self.entries as u16).to_le_bytes());
+        end.extend_from_slice(&(self.entries as u16).to_le_bytes());
+        end.extend_from_slice(&central_size.to_le_bytes());
+        end.extend_from_slice(&central_offset.to_le_bytes());
+        end.extend_from_slice(&0u16.to_le_bytes()); // comment length
+        self.out.write_all(&self.central)?;
+        self.out.write_all(&end)
+    }
+}
+
+/// CRC-32 (IEEE) checksum as used by zip archives
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod testing {
+    use super::{
+        crc32, npy_header, to_npy_batch, to_npz, write_npy_batch, write_npz, NpyDtype, ZipWriter,
+        MAX_ENTRIES,
+    };
+    use crate::{ConversionError, DiGraph, Graph, GraphReader};
+    use std::io;
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn test_header_alignment() {
+        for shape in [&[2, 2][..], &[100, 30, 30], &[5]] {
+            let header = npy_header(NpyDtype::F32, shape);
+            assert_eq!(header.len() % 64, 0);
+            assert_eq!(header.last(), Some(&b'\n'));
+            let len = u16::from_le_bytes([header[8], header[9]]) as usize;
+            assert_eq!(len + 10, header.len());
+        }
+    }
+
+    #[test]
+    fn test_header_dict() {
+        let header = npy_header(NpyDtype::U8, &[3, 2, 2]);
+        let dict = std::str::from_utf8(&header[10..]).unwrap();
+        assert!(dict.starts_with("{'descr': '|u1', 'fortran_order': False, 'shape': (3, 2, 2), }"));
+    }
+
+    #[test]
+    fn test_batch() {
+        let graphs = vec![
+            DiGraph::from_d6("&AG").unwrap(),
+            DiGraph::from_d6("&AW").unwrap(),
+        ];
+        let npy = to_npy_batch(&graphs, NpyDtype::U8).unwrap();
+        assert_eq!(&npy[npy.len() - 8..], &[0, 0, 1, 0, 0, 1, 1, 0]);
+    }
+
+    #[test]
+    fn test_batch_from_reader() {
+        let reader = GraphReader::new(&b"A_\nA?\n"[..]);
+        let graphs = reader.graphs::<Graph>().filter_map(Result::ok);
+        let npy = to_npy_batch(graphs, NpyDtype::U8).unwrap();
+        assert_eq!(&npy[npy.len() - 8..], &[0, 1, 1, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_batch_mismatched_sizes() {
+        let graphs = vec![Graph::from_g6("A_").unwrap(), Graph::from_g6("Bw").unwrap()];
+        let npy = to_npy_batch(&graphs, NpyDtype::U8);
+        assert_eq!(npy, Err(ConversionError::MismatchedGraphSizes));
+        let mut out = Vec::new();
+        let err = write_npy_batch(&mut out, &graphs, NpyDtype::U8).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_write_matches_to() {
+        let graphs = vec![Graph::from_g6("A_").unwrap(), Graph::from_g6("A?").unwrap()];
+        let mut out = Vec::new();
+        write_npy_batch(&mut out, &graphs, NpyDtype::F32).unwrap();
+        assert_eq!(out, to_npy_batch(&graphs, NpyDtype::F32).unwrap());
+        out.clear();
+        write_npz(&mut out, &graphs, NpyDtype::U8).unwrap();
+        assert_eq!(out, to_npz(&graphs, NpyDtype::U8).unwrap());
+    }
+
+    #[test]
+    fn test_npz_too_many_entries() {
+        let graph = Graph::from_g6("?").unwrap();
+        let graphs = (0..MAX_ENTRIES + 1).map(|_| &graph);
+        let err = write_npz(&mut io::sink(), graphs, NpyDtype::U8).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        let graphs = (0..MAX_ENTRIES).map(|_| &graph);
+        assert!(write_npz(&mut io::sink(), graphs, NpyDtype::U8).is_ok());
+    }
+
+    #[test]
+    fn test_zip_offset_limit() {
+        let mut sink = io::sink();
+        let mut zip = ZipWriter::new(&mut sink);
+        zip.offset = u32::MAX as usize - 1;
+        assert!(zip.add("a", b"data").is_ok());
+        let err = zip.add("b", b"data").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(zip.finish().is_err());
+    }
+
+    #[test]
+    fn test_npz_entries() {
+        let graphs = vec![Graph::from_g6("A_").unwrap(), Graph::from_g6("Bw").unwrap()];
+        let npz = to_npz(&graphs, NpyDtype::U8).unwrap();
+        let count = u16::from_le_bytes([npz[npz.len() - 12], npz[npz.len() - 11]]);
+        assert_eq!(count, 2);
+        assert!(npz.windows(9).any(|w| w == b"arr_1.npy"));
+        assert_eq!(
+            &npz[npz.len() - 22..npz.len() - 18],
+            &[0x50, 0x4b, 0x05, 0x06]
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use crate::Layout;
+
+/// Options for the edge list export
+///
+/// # Example
+/// ```
+/// use graph6_rs::{EdgeListOptions, Graph, GraphConversion};
+/// let graph = Graph::from_g6("Bw").unwrap();
+/// let options = EdgeListOptions::new().separator(",").one_based(true).header(true);
+/// assert_eq!(graph.to_edge_list(&options), "3,3\n1,2\n1,3\n2,3\n");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeListOptions {
+    pub(crate) separator: String,
+    pub(crate) one_based: bool,
+    pub(crate) header: bool,
+}
+impl Default for EdgeListOptions {
+    fn default() -> Self {
+        Self {
+            separator: " ".to_string(),
+            one_based: false,
+            header: false,
+        }
+    }
+}
+impl EdgeListOptions {
+    /// Creates the default options (space separated, 0-based, no header)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the separator between the two vertices of an edge
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Sets whether vertices are numbered from 1 instead of 0
+    pub fn one_based(mut self, one_based: bool) -> Self {
+        self.one_based = one_based;
+        self
+    }
+
+    /// Sets whether a header line with the vertex and edge counts is written
+    pub fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+}
+
+/// Problem line variants of the DIMACS graph format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DimacsFormat {
+    /// `p edge n m`, used by clique solvers
+    #[default]
+    Edge,
+    /// `p col n m`, used by graph colouring solvers
+    Col,
+}
+impl DimacsFormat {
+    /// Returns the problem name written on the `p` line
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Edge => "edge",
+            Self::Col => "col",
+        }
+    }
+}
+
+/// Output layouts of nauty's `listg` tool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ListgFormat {
+    /// Adjacency lists, as written by `listg` without options
+    #[default]
+    List,
+    /// Adjacency matrix rows of `0` and `1`, as written by `listg -a`
+    Matrix,
+    /// Vertex and edge counts followed by the edges, as written by `listg -e`
+    Edges,
+}
+
+/// Options for the TikZ export
+///
+/// # Example
+/// ```
+/// use graph6_rs::{Graph, GraphConversion, Layout, TikzOptions};
+/// let graph = Graph::from_g6("A_").unwrap();
+/// let options = TikzOptions::new()
+///     .layout(Layout::Circular)
+///     .scale(1.5)
+///     .node_style("circle, fill=black, inner sep=1pt");
+/// let tikz = graph.to_tikz(&options);
+/// assert!(tikz.starts_with("\\begin{tikzpicture}"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TikzOptions {
+    pub(crate) layout: Layout,
+    pub(crate) scale: f64,
+    pub(crate) node_style: String,
+    pub(crate) edge_style: String,
+    pub(crate) labels: bool,
+}
+impl Default for TikzOptions {
+    fn default() -> Self {
+        Self {
+            layout: Layout::default(),
+            scale: 2.0,
+            node_style: "circle, draw, inner sep=2pt".to_string(),
+            edge_style: String::new(),
+            labels: true,
+        }
+    }
+}
+impl TikzOptions {
+    /// Creates the default options (circular layout of radius 2, labelled circles)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the layout used to place the vertices
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Sets the factor by which layout coordinates are scaled
+    pub fn scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Sets the TikZ style of every node
+    pub fn node_style(mut self, style: &str) -> Self {
+        self.node_style = style.to_string();
+        self
+    }
+
+    /// Sets the TikZ style of every edge
+    pub fn edge_style(mut self, style: &str) -> Self {
+        self.edge_style = style.to_string();
+        self
+    }
+
+    /// Sets whether vertices are labelled with their index
+    pub fn labels(mut self, labels: bool) -> Self {
+        self.labels = labels;
+        self
+    }
+}
+
+/// Options for the Pajek NET export
+///
+/// # Example
+/// ```
+/// use graph6_rs::{Graph, GraphConversion, NetOptions};
+/// let graph = Graph::from_g6("Bw").unwrap();
+/// let options = NetOptions::new().labels(["a", "b", "c"]).compact(true);
+/// assert_eq!(
+///     graph.to_net_with(&options),
+///     "*Vertices 3\n1 \"a\"\n2 \"b\"\n3 \"c\"\n*Edgeslist\n1 2 3\n2 3\n"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NetOptions {
+    pub(crate) labels: Vec<String>,
+    pub(crate) positions: Vec<(f64, f64)>,
+    pub(crate) compact: bool,
+}
+impl NetOptions {
+    /// Creates the default options (index labels, no coordinates, one line per edge)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the label of every vertex, in vertex order
+    ///
+    /// Vertices without a label are labelled with their 0-based index.
+    /// Quotes and backslashes inside labels are escaped with a backslash.
+    pub fn labels<I, S>(mut self, labels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.labels = labels.into_iter().map(|label| label.to_string()).collect();
+        self
+    }
+
+    /// Sets vertex coordinates in `[-1, 1]`, e.g. from [`Layout::positions`]
+    pub fn positions(mut self, positions: &[(f64, f64)]) -> Self {
+        self.positions = positions.to_vec();
+        self
+    }
+
+    /// Sets whether edges are written as `*Edgeslist`/`*Arcslist` with one
+    /// line per vertex instead of one line per edge
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+}
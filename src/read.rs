@@ -0,0 +1,504 @@
+use crate::{compress::decompress, DiGraph, Graph, IOError};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+/// Optional headers which may precede the first graph of a file
+const HEADERS: [&str; 2] = [">>graph6<<", ">>digraph6<<"];
+
+/// Trait to read graphs from graph6 formatted strings
+pub trait ReadGraph: Sized {
+    fn read_graph(repr: &str) -> Result<Self, IOError>;
+}
+
+/// Reads graph6/digraph6 formatted graphs line by line from a buffered reader
+///
+/// Empty lines are skipped and an optional `>>graph6<<` or `>>digraph6<<`
+/// header on the first line is removed.
+///
+/// # Example
+/// ```
+/// use graph6_rs::{Graph, GraphReader};
+/// let input = "A_\nBw\n".as_bytes();
+/// let graphs: Vec<Graph> = GraphReader::new(input)
+///     .graphs()
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(graphs.len(), 2);
+/// assert_eq!(graphs[1].n, 3);
+/// ```
+#[derive(Debug)]
+pub struct GraphReader<R> {
+    inner: R,
+    buf: Vec<u8>,
+    line: usize,
+}
+impl<R: BufRead> GraphReader<R> {
+    /// Creates a new reader over a buffered input
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            line: 0,
+        }
+    }
+
+    /// Returns the (1-based) line number of the most recently read line
+    pub fn line_number(&self) -> usize {
+        self.line
+    }
+
+    /// Reads the next encoded graph without decoding it
+    ///
+    /// Returns `Ok(None)` once the input is exhausted. A line which is not
+    /// valid UTF-8 is returned as an [`io::ErrorKind::InvalidData`] error,
+    /// after which reading continues with the following line.
+    pub fn next_line(&mut self) -> io::Result<Option<&str>> {
+        match self.next_bytes()? {
+            Some(bytes) => std::str::from_utf8(bytes)
+                .map(Some)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)),
+            None => Ok(None),
+        }
+    }
+
+    /// Reads the next non-empty line as bytes, without line ending or header
+    fn next_bytes(&mut self) -> io::Result<Option<&[u8]>> {
+        loop {
+            self.buf.clear();
+            if self.inner.read_until(b'\n', &mut self.buf)? == 0 {
+                return Ok(None);
+            }
+            self.line += 1;
+            let mut end = self.buf.len();
+            while end > 0 && matches!(self.buf[end - 1], b'\n' | b'\r') {
+                end -= 1;
+            }
+            let start = if self.line == 1 {
+                header_len(&self.buf[..end])
+            } else {
+                0
+            };
+            if start < end {
+                return Ok(Some(&self.buf[start..end]));
+            }
+        }
+    }
+
+    /// Returns an iterator over the encoded graphs
+    pub fn lines(self) -> Lines<R> {
+        Lines { reader: self }
+    }
+
+    /// Reads and decodes the next graph
+    ///
+    /// Returns `None` once the input is exhausted. Lines which are not
+    /// valid UTF-8 fail with [`IOError::NonCanonicalEncoding`].
+    pub fn next_graph<G: ReadGraph>(&mut self) -> Option<Result<G, ReadError>> {
+        let decoded = match self.next_bytes() {
+            Ok(Some(bytes)) => decode::<G>(bytes),
+            Ok(None) => return None,
+            Err(err) => return Some(Err(ReadError::Io(err))),
+        };
+        Some(decoded.map_err(|(error, _)| ReadError::Parse {
+            line: self.line,
+            error,
+        }))
+    }
+
+    /// Returns an iterator decoding each line into a graph
+    ///
+    /// The iterator is fail-fast: it ends after yielding the first error.
+    pub fn graphs<G: ReadGraph>(self) -> Graphs<R, G> {
+        Graphs {
+            reader: self,
+            failed: false,
+            _graph: std::marker::PhantomData,
+        }
+    }
+
+    /// Returns an iterator which skips lines that fail to decode
+    ///
+    /// The skipped lines are collected and can be retrieved with
+    /// [`SkippingGraphs::errors`] once iteration is complete. Errors of the
+    /// underlying reader are still yielded and end the iteration.
+    ///
+    /// # Example
+    /// ```
+    /// use graph6_rs::{Graph, GraphReader};
+    /// let input = "A_\nA1\nBw\n".as_bytes();
+    /// let mut graphs = GraphReader::new(input).graphs_skipping::<Graph>();
+    /// assert_eq!(graphs.by_ref().count(), 2);
+    /// assert_eq!(graphs.errors()[0].line, 2);
+    /// ```
+    pub fn graphs_skipping<G: ReadGraph>(self) -> SkippingGraphs<R, G> {
+        SkippingGraphs {
+            reader: self,
+            errors: Vec::new(),
+            failed: false,
+            _graph: std::marker::PhantomData,
+        }
+    }
+
+    /// Returns an iterator which reports lines that fail to decode to a
+    /// callback and continues with the next line
+    ///
+    /// # Example
+    /// ```
+    /// use graph6_rs::{Graph, GraphReader};
+    /// let input = "A_\nA1\nBw\n".as_bytes();
+    /// let mut bad = Vec::new();
+    /// let graphs: Vec<Graph> = GraphReader::new(input)
+    ///     .graphs_with(|err| bad.push(err.line))
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    /// assert_eq!(graphs.len(), 2);
+    /// assert_eq!(bad, vec![2]);
+    /// ```
+    pub fn graphs_with<G, F>(self, on_error: F) -> CallbackGraphs<R, G, F>
+    where
+        G: ReadGraph,
+        F: FnMut(LineError),
+    {
+        CallbackGraphs {
+            reader: self,
+            on_error,
+            failed: false,
+            _graph: std::marker::PhantomData,
+        }
+    }
+
+    /// Reads until a graph decodes successfully, reporting every line
+    /// which fails to decode
+    fn next_valid<G, F>(&mut self, on_error: &mut F) -> Option<io::Result<G>>
+    where
+        G: ReadGraph,
+        F: FnMut(LineError),
+    {
+        loop {
+            let decoded = match self.next_bytes() {
+                Ok(Some(bytes)) => decode::<G>(bytes),
+                Ok(None) => return None,
+                Err(err) => return Some(Err(err)),
+            };
+            match decoded {
+                Ok(graph) => return Some(Ok(graph)),
+                Err((error, repr)) => on_error(LineError {
+                    line: self.line,
+                    repr,
+                    error,
+                }),
+            }
+        }
+    }
+
+    /// Consumes the reader and returns the underlying input
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl GraphReader<Box<dyn BufRead>> {
+    /// Opens a graph file for reading
+    ///
+    /// Gzip and zstd compressed files are detected by their magic bytes and
+    /// decompressed transparently (requires the `gzip`/`zstd` features).
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = BufReader::new(File::open(path)?);
+        Ok(Self::new(decompress(file)?))
+    }
+}
+
+/// Iterator over the encoded graphs of a [`GraphReader`]
+#[derive(Debug)]
+pub struct Lines<R> {
+    reader: GraphReader<R>,
+}
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.reader
+            .next_line()
+            .map(|line| line.map(String::from))
+            .transpose()
+    }
+}
+
+/// Fail-fast iterator over the decoded graphs of a [`GraphReader`]
+#[derive(Debug)]
+pub struct Graphs<R, G> {
+    reader: GraphReader<R>,
+    failed: bool,
+    _graph: std::marker::PhantomData<G>,
+}
+impl<R: BufRead, G: ReadGraph> Iterator for Graphs<R, G> {
+    type Item = Result<G, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let next = self.reader.next_graph();
+        self.failed = matches!(next, Some(Err(_)));
+        next
+    }
+}
+
+/// Iterator over the decoded graphs of a [`GraphReader`] which skips and
+/// collects lines that fail to decode
+#[derive(Debug)]
+pub struct SkippingGraphs<R, G> {
+    reader: GraphReader<R>,
+    errors: Vec<LineError>,
+    failed: bool,
+    _graph: std::marker::PhantomData<G>,
+}
+impl<R, G> SkippingGraphs<R, G> {
+    /// Returns the lines skipped so far
+    pub fn errors(&self) -> &[LineError] {
+        &self.errors
+    }
+
+    /// Consumes the iterator and returns the lines skipped
+    pub fn into_errors(self) -> Vec<LineError> {
+        self.errors
+    }
+}
+impl<R: BufRead, G: ReadGraph> Iterator for SkippingGraphs<R, G> {
+    type Item = io::Result<G>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let errors = &mut self.errors;
+        let next = self.reader.next_valid(&mut |err| errors.push(err));
+        self.failed = matches!(next, Some(Err(_)));
+        next
+    }
+}
+
+/// Iterator over the decoded graphs of a [`GraphReader`] which reports
+/// lines that fail to decode to a callback
+pub struct CallbackGraphs<R, G, F> {
+    reader: GraphReader<R>,
+    on_error: F,
+    failed: bool,
+    _graph: std::marker::PhantomData<G>,
+}
+impl<R, G, F> Iterator for CallbackGraphs<R, G, F>
+where
+    R: BufRead,
+    G: ReadGraph,
+    F: FnMut(LineError),
+{
+    type Item = io::Result<G>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let next = self.reader.next_valid(&mut self.on_error);
+        self.failed = matches!(next, Some(Err(_)));
+        next
+    }
+}
+
+/// A line which failed to decode
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError {
+    /// 1-based line number in the input
+    pub line: usize,
+    /// The encoded graph as it appeared on the line
+    pub repr: String,
+    /// The decoding error
+    pub error: IOError,
+}
+
+/// Errors which can occur while reading graphs from a stream
+#[derive(Debug)]
+pub enum ReadError {
+    /// The underlying reader failed
+    Io(io::Error),
+    /// A line could not be decoded
+    Parse { line: usize, error: IOError },
+}
+impl From<io::Error> for ReadError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl ReadGraph for Graph {
+    fn read_graph(repr: &str) -> Result<Self, IOError> {
+        Self::from_g6(repr)
+    }
+}
+
+impl ReadGraph for DiGraph {
+    fn read_graph(repr: &str) -> Result<Self, IOError> {
+        Self::from_d6(repr)
+    }
+}
+
+/// Decodes a line into a graph, returning the error and the line on failure
+///
+/// Lines which are not valid UTF-8 cannot be graph6 encodings and are
+/// reported as non-canonical.
+pub(crate) fn decode<G: ReadGraph>(bytes: &[u8]) -> Result<G, (IOError, String)> {
+    match std::str::from_utf8(bytes) {
+        Ok(repr) => G::read_graph(repr).map_err(|error| (error, repr.to_string())),
+        Err(_) => Err((
+            IOError::NonCanonicalEncoding,
+            String::from_utf8_lossy(bytes).into_owned(),
+        )),
+    }
+}
+
+/// Returns the length of an optional file header at the start of a line
+pub(crate) fn header_len(line: &[u8]) -> usize {
+    HEADERS
+        .iter()
+        .find(|header| line.starts_with(header.as_bytes()))
+        .map_or(0, |header| header.len())
+}
+
+#[cfg(test)]
+mod testing {
+    use super::{GraphReader, LineError, ReadError};
+    use crate::{DiGraph, Graph, IOError};
+
+    #[test]
+    fn test_lines() {
+        let input = "A_\n\nBw\r\nC~".as_bytes();
+        let lines: Vec<String> = GraphReader::new(input)
+            .lines()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(lines, vec!["A_", "Bw", "C~"]);
+    }
+
+    #[test]
+    fn test_lines_header() {
+        let input = ">>graph6<<A_\nBw\n".as_bytes();
+        let lines: Vec<String> = GraphReader::new(input)
+            .lines()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(lines, vec!["A_", "Bw"]);
+    }
+
+    #[test]
+    fn test_graphs() {
+        let input = "A_\nBw\n".as_bytes();
+        let graphs: Vec<Graph> = GraphReader::new(input)
+            .graphs()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(graphs[0].bit_vec, &[0, 1, 1, 0]);
+        assert_eq!(graphs[1].n, 3);
+    }
+
+    #[test]
+    fn test_digraphs() {
+        let input = ">>digraph6<<&AG\n&B\\o\n".as_bytes();
+        let graphs: Vec<DiGraph> = GraphReader::new(input)
+            .graphs()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(graphs[0].bit_vec, &[0, 0, 1, 0]);
+        assert_eq!(graphs[1].n, 3);
+    }
+
+    #[test]
+    fn test_graphs_error_line() {
+        let input = "A_\n\nA1\n".as_bytes();
+        let mut graphs = GraphReader::new(input).graphs::<Graph>();
+        assert!(graphs.next().unwrap().is_ok());
+        match graphs.next().unwrap() {
+            Err(ReadError::Parse { line, error }) => {
+                assert_eq!(line, 3);
+                assert_eq!(error, IOError::NonCanonicalEncoding);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_graphs_fail_fast() {
+        let input = "A1\nA_\n".as_bytes();
+        let mut graphs = GraphReader::new(input).graphs::<Graph>();
+        assert!(graphs.next().unwrap().is_err());
+        assert!(graphs.next().is_none());
+    }
+
+    #[test]
+    fn test_graphs_skipping() {
+        let input = &b"A_\nA1\nBw\n~\nB\xffw\n?\nC~\n"[..];
+        let mut graphs = GraphReader::new(input).graphs_skipping::<Graph>();
+        let sizes: Vec<usize> = graphs.by_ref().map(|g| g.unwrap().n).collect();
+        assert_eq!(sizes, vec![2, 3, 0, 4]);
+        assert_eq!(
+            graphs.into_errors(),
+            vec![
+                LineError {
+                    line: 2,
+                    repr: "A1".to_string(),
+                    error: IOError::NonCanonicalEncoding,
+                },
+                LineError {
+                    line: 4,
+                    repr: "~".to_string(),
+                    error: IOError::GraphTooLarge,
+                },
+                LineError {
+                    line: 5,
+                    repr: "B\u{fffd}w".to_string(),
+                    error: IOError::NonCanonicalEncoding,
+                },
+            ]
+        );
+
+        let input = "&\n&AG\n&C\n&?\n".as_bytes();
+        let mut graphs = GraphReader::new(input).graphs_skipping::<DiGraph>();
+        let sizes: Vec<usize> = graphs.by_ref().map(|g| g.unwrap().n).collect();
+        assert_eq!(sizes, vec![2, 0]);
+        let errors: Vec<(usize, IOError)> = graphs
+            .into_errors()
+            .into_iter()
+            .map(|err| (err.line, err.error))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                (1, IOError::InvalidSizeChar),
+                (3, IOError::NonCanonicalEncoding)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_next_line_invalid_utf8() {
+        let input = &b"A_\n\xff\nBw\n"[..];
+        let mut reader = GraphReader::new(input);
+        assert_eq!(reader.next_line().unwrap(), Some("A_"));
+        let err = reader.next_line().unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(reader.next_line().unwrap(), Some("Bw"));
+    }
+
+    #[test]
+    fn test_graphs_with() {
+        let input = "&AG\nA_\n&AG\n".as_bytes();
+        let mut lines = Vec::new();
+        let count = GraphReader::new(input)
+            .graphs_with::<DiGraph, _>(|err| lines.push((err.line, err.error)))
+            .filter(Result::is_ok)
+            .count();
+        assert_eq!(count, 2);
+        assert_eq!(lines, vec![(2, IOError::InvalidDigraphHeader)]);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::utils::SplitMix64;
+use std::ops::{Bound, RangeBounds};
+
+/// Utilities for selecting graphs from a stream
+///
+/// These adapters work on any iterator, so applying them to
+/// [`GraphReader::lines`](crate::GraphReader::lines) selects graphs
+/// without decoding the ones which are discarded.
+///
+/// # Example
+/// ```
+/// use graph6_rs::{GraphReader, GraphStream};
+/// let input = "A_\nBw\nC~\nA?\n".as_bytes();
+/// let lines: Vec<String> = GraphReader::new(input)
+///     .lines()
+///     .res_mod(1, 2)
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(lines, vec!["Bw", "A?"]);
+/// ```
+pub trait GraphStream: Iterator + Sized {
+    /// Selects every item whose (0-based) index is congruent to `res`
+    /// modulo `m`, matching the `res/mod` argument of the nauty tools
+    ///
+    /// # Panics
+    /// Panics if `m` is zero or `res >= m`.
+    fn res_mod(self, res: usize, m: usize) -> ResMod<Self> {
+        assert!(res < m, "res/mod requires 0 <= res < mod");
+        ResMod {
+            iter: self,
+            res,
+            m,
+            idx: 0,
+        }
+    }
+
+    /// Selects the items whose (0-based) index lies within a range
+    fn graph_range<B: RangeBounds<usize>>(
+        self,
+        range: B,
+    ) -> std::iter::Take<std::iter::Skip<Self>> {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => usize::MAX,
+        };
+        self.skip(start).take(end.saturating_sub(start))
+    }
+
+    /// Takes a uniform random sample of `k` items using reservoir sampling
+    ///
+    /// The sample is returned in stream order and is reproducible for a
+    /// given seed. If the stream has fewer than `k` items all are returned.
+    fn reservoir_sample(self, k: usize, seed: u64) -> Vec<Self::Item> {
+        let mut rng = SplitMix64::new(seed);
+        let mut reservoir = Vec::with_capacity(k);
+        for (idx, item) in self.enumerate() {
+            if reservoir.len() < k {
+                reservoir.push((idx, item));
+            } else {
+                let slot = rng.next_below(idx as u64 + 1) as usize;
+                if slot < k {
+                    reservoir[slot] = (idx, item);
+                }
+            }
+        }
+        reservoir.sort_by_key(|(idx, _)| *idx);
+        reservoir.into_iter().map(|(_, item)| item).collect()
+    }
+}
+impl<I: Iterator> GraphStream for I {}
+
+/// Iterator selecting items by their index modulo `m`
+#[derive(Debug, Clone)]
+pub struct ResMod<I> {
+    iter: I,
+    res: usize,
+    m: usize,
+    idx: usize,
+}
+impl<I: Iterator> Iterator for ResMod<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.iter.next()?;
+            let idx = self.idx;
+            self.idx += 1;
+            if idx % self.m == self.res {
+                return Some(item);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::GraphStream;
+
+    #[test]
+    fn test_res_mod() {
+        let selected: Vec<usize> = (0..10).res_mod(2, 3).collect();
+        assert_eq!(selected, vec![2, 5, 8]);
+    }
+
+    #[test]
+    fn test_res_mod_partition() {
+        let mut all: Vec<usize> = (0..3).flat_map(|res| (0..20).res_mod(res, 3)).collect();
+        all.sort();
+        assert_eq!(all, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_res_mod_invalid() {
+        let _ = (0..10).res_mod(3, 3);
+    }
+
+    #[test]
+    fn test_graph_range() {
+        assert_eq!((0..10).graph_range(2..5).collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!((0..10).graph_range(8..).collect::<Vec<_>>(), vec![8, 9]);
+        assert_eq!((0..10).graph_range(..=1).collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!((0..10).graph_range(12..).count(), 0);
+    }
+
+    #[test]
+    fn test_graph_range_max_bounds() {
+        use std::ops::Bound;
+        assert_eq!(
+            (0..3).graph_range(..=usize::MAX).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        let range = (Bound::Excluded(usize::MAX), Bound::Unbounded);
+        assert_eq!((0..3).graph_range(range).count(), 0);
+    }
+
+    #[test]
+    fn test_reservoir_sample() {
+        let sample = (0..1000).reservoir_sample(10, 42);
+        assert_eq!(sample.len(), 10);
+        assert!(sample.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(sample, (0..1000).reservoir_sample(10, 42));
+        assert_ne!(sample, (0..1000).reservoir_sample(10, 43));
+    }
+
+    #[test]
+    fn test_reservoir_sample_short() {
+        assert_eq!((0..3).reservoir_sample(10, 0), vec![0, 1, 2]);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{conversion::render, GraphConversion, Layout};
+use std::fmt;
+
+/// Options for the SVG rendering
+///
+/// # Example
+/// ```
+/// use graph6_rs::{Graph, GraphConversion, Layout, SvgOptions};
+/// let graph = Graph::from_g6("Bw").unwrap();
+/// let options = SvgOptions::new().layout(Layout::Bipartite).size(200.0);
+/// let svg = graph.to_svg(&options);
+/// assert!(svg.starts_with("<svg "));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOptions {
+    pub(crate) layout: Layout,
+    pub(crate) size: f64,
+    pub(crate) node_radius: f64,
+    pub(crate) labels: bool,
+}
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            layout: Layout::default(),
+            size: 300.0,
+            node_radius: 10.0,
+            labels: true,
+        }
+    }
+}
+impl SvgOptions {
+    /// Creates the default options (circular layout, 300px, labelled vertices)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the layout used to place the vertices
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Sets the width and height of a single drawing in pixels
+    pub fn size(mut self, size: f64) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets the radius of the vertex circles in pixels
+    pub fn node_radius(mut self, radius: f64) -> Self {
+        self.node_radius = radius;
+        self
+    }
+
+    /// Sets whether vertices are labelled with their index
+    pub fn labels(mut self, labels: bool) -> Self {
+        self.labels = labels;
+        self
+    }
+}
+
+/// Renders many graphs as a single SVG sheet arranged in a grid
+///
+/// # Arguments
+/// * `graphs` - The graphs to render, one per cell
+/// * `options` - Rendering options applied to every cell
+/// * `columns` - Number of cells per row (at least one)
+///
+/// # Example
+/// ```
+/// use graph6_rs::{to_svg_grid, Graph, SvgOptions};
+/// let graphs = vec![Graph::from_g6("A_").unwrap(), Graph::from_g6("Bw").unwrap()];
+/// let svg = to_svg_grid(&graphs, &SvgOptions::new().size(100.0), 2);
+/// assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"200\" height=\"100\""));
+/// ```
+pub fn to_svg_grid<I>(graphs: I, options: &SvgOptions, columns: usize) -> String
+where
+    I: IntoIterator,
+    I::Item: GraphConversion,
+{
+    render(|out| write_svg_grid(out, graphs, options, columns))
+}
+
+/// Writes many graphs as a single SVG sheet arranged in a grid
+///
+/// See [`to_svg_grid`] for the arguments.
+pub fn write_svg_grid<I>(
+    out: &mut dyn fmt::Write,
+    graphs: I,
+    options: &SvgOptions,
+    columns: usize,
+) -> fmt::Result
+where
+    I: IntoIterator,
+    I::Item: GraphConversion,
+{
+    let graphs: Vec<I::Item> = graphs.into_iter().collect();
+    let columns = columns.max(1);
+    let rows = graphs.len().div_ceil(columns).max(1);
+    let directed = graphs.iter().any(|graph| graph.is_directed());
+
+    write_svg_header(
+        out,
+        options.size * columns.min(graphs.len().max(1)) as f64,
+        options.size * rows as f64,
+        directed,
+    )?;
+    for (k, graph) in graphs.into_iter().enumerate() {
+        let x = options.size * (k % columns) as f64;
+        let y = options.size * (k / columns) as f64;
+        writeln!(out, "<g transform=\"translate({} {})\">", x, y)?;
+        write_svg_graph(out, &graph, options)?;
+        out.write_str("</g>\n")?;
+    }
+    out.write_str("</svg>\n")
+}
+
+/// Writes a single graph as an SVG document
+pub(crate) fn write_svg<G: GraphConversion + ?Sized>(
+    graph: &G,
+    out: &mut dyn fmt::Write,
+    options: &SvgOptions,
+) -> fmt::Result {
+    write_svg_header(out, options.size, options.size, graph.is_directed())?;
+    write_svg_graph(out, graph, options)?;
+    out.write_str("</svg>\n")
+}
+
+/// Opens an SVG document, defining an arrowhead marker if required
+fn write_svg_header(
+    out: &mut dyn fmt::Write,
+    width: f64,
+    height: f64,
+    arrows: bool,
+) -> fmt::Result {
+    writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">",
+        width, height, width, height
+    )?;
+    if arrows {
+        out.write_str(
+            "<defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" \
+             markerWidth=\"6\" markerHeight=\"6\" orient=\"auto-start-reverse\">\
+             <path d=\"M 0 0 L 10 5 L 0 10 z\"/></marker></defs>\n",
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes the edges and vertices of a graph drawn in a `size` square
+fn write_svg_graph<G: GraphConversion + ?Sized>(
+    out: &mut dyn fmt::Write,
+    graph: &G,
+    options: &SvgOptions,
+) -> fmt::Result {
+    let r = options.node_radius;
+    let margin = 2.0 * r;
+    let half = (options.size - 2.0 * margin) / 2.0;
+    let points: Vec<(f64, f64)> = options
+        .layout
+        .positions(graph)
+        .into_iter()
+        .map(|(x, y)| (margin + half * (x + 1.0), margin + half * (1.0 - y)))
+        .collect();
+
+    // include edges
+    let marker = if graph.is_directed() {
+        " marker-end=\"url(#arrow)\""
+    } else {
+        ""
+    };
+    for (u, v) in graph.edges() {
+        let (x1, y1) = points[u];
+        let (x2, y2) = points[v];
+        if u == v {
+            writeln!(
+                out,
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"none\" stroke=\"black\"/>",
+                x1,
+                y1 - r,
+                r * 0.8
+            )?;
+            continue;
+        }
+        // stop the line at the boundary of the target circle
+        let (dx, dy) = (x2 - x1, y2 - y1);
+        let len = (dx * dx + dy * dy).sqrt().max(1e-9);
+        let (x2, y2) = if graph.is_directed() {
+            (x2 - dx / len * r, y2 - dy / len * r)
+        } else {
+            (x2, y2)
+        };
+        writeln!(
+            out,
+            "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"black\"{}/>",
+            x1, y1, x2, y2, marker
+        )?;
+    }
+
+    // include vertices
+    for (i, (x, y)) in points.iter().enumerate() {
+        writeln!(
+            out,
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"white\" stroke=\"black\"/>",
+            x, y, r
+        )?;
+        if options.labels {
+            writeln!(
+                out,
+                "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"{:.1}\" text-anchor=\"middle\" \
+                 dominant-baseline=\"central\">{}</text>",
+                x, y, r, i
+            )?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod testing {
+    use super::{to_svg_grid, SvgOptions};
+    use crate::{DiGraph, Graph, GraphConversion};
+
+    #[test]
+    fn test_grid_dimensions() {
+        let graphs: Vec<Graph> = ["A_", "Bw", "C~"]
+            .iter()
+            .map(|repr| Graph::from_g6(repr).unwrap())
+            .collect();
+        let svg = to_svg_grid(&graphs, &SvgOptions::new().size(100.0), 2);
+        assert!(svg.starts_with(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"200\" height=\"200\" viewBox=\"0 0 200 200\">\n"
+        ));
+        assert_eq!(svg.matches("<g transform=").count(), 3);
+        assert!(svg.contains("<g transform=\"translate(0 100)\">"));
+        assert_eq!(svg.matches("<line ").count(), 1 + 3 + 6);
+        assert!(!svg.contains("<marker"));
+    }
+
+    #[test]
+    fn test_grid_directed_marker() {
+        let graphs = vec![DiGraph::from_d6("&AG").unwrap()];
+        let svg = to_svg_grid(&graphs, &SvgOptions::new(), 4);
+        assert!(svg.contains("width=\"300\" height=\"300\""));
+        assert_eq!(svg.matches("<marker id=\"arrow\"").count(), 1);
+    }
+
+    #[test]
+    fn test_self_loop() {
+        let graph = DiGraph::from_adj(&[1]).unwrap();
+        let svg = graph.to_svg(&SvgOptions::new().labels(false));
+        assert_eq!(svg.matches("<circle ").count(), 2);
+        assert!(!svg.contains("<text"));
+    }
+}
@@ -0,0 +1,257 @@
+use crate::GraphConversion;
+use std::fmt;
+
+/// A user-defined text format rendered by [`GraphConversion::to_template`]
+///
+/// The output is the header, one vertex part per vertex, one edge part per
+/// edge and the footer. Parts are written as given, so lines must end with
+/// an explicit `\n`. The placeholders are
+///
+/// | Placeholder  | Value                                         | Parts        |
+/// |--------------|-----------------------------------------------|--------------|
+/// | `{n}`        | number of vertices                            | all          |
+/// | `{m}`        | number of edges (undirected edges count once) | all          |
+/// | `{directed}` | `true` or `false`                             | all          |
+/// | `{u}`        | source vertex                                 | edge         |
+/// | `{v}`        | the vertex, or the target vertex              | vertex, edge |
+///
+/// Vertices are numbered from 0 unless [`Template::one_based`] is set.
+/// `{{` and `}}` produce literal braces. Unknown placeholders, and vertex
+/// placeholders outside the parts listed above, are written unchanged.
+///
+/// # Example
+/// ```
+/// use graph6_rs::{DimacsFormat, Graph, GraphConversion, Template};
+/// let graph = Graph::from_g6("Bw").unwrap();
+/// let template = Template::new()
+///     .header("p edge {n} {m}\n")
+///     .edge("e {u} {v}\n")
+///     .one_based(true);
+/// assert_eq!(graph.to_template(&template), graph.to_dimacs(DimacsFormat::Edge).unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Template {
+    header: String,
+    vertex: String,
+    edge: String,
+    footer: String,
+    one_based: bool,
+}
+impl Template {
+    /// Creates an empty template (0-based, no output)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the text written once before the vertices
+    pub fn header(mut self, header: &str) -> Self {
+        self.header = header.to_string();
+        self
+    }
+
+    /// Sets the text written for every vertex
+    pub fn vertex(mut self, vertex: &str) -> Self {
+        self.vertex = vertex.to_string();
+        self
+    }
+
+    /// Sets the text written for every edge
+    pub fn edge(mut self, edge: &str) -> Self {
+        self.edge = edge.to_string();
+        self
+    }
+
+    /// Sets the text written once after the edges
+    pub fn footer(mut self, footer: &str) -> Self {
+        self.footer = footer.to_string();
+        self
+    }
+
+    /// Sets whether vertices are numbered from 1
+    pub fn one_based(mut self, one_based: bool) -> Self {
+        self.one_based = one_based;
+        self
+    }
+}
+
+/// A piece of a parsed template part
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Segment<'a> {
+    Text(&'a str),
+    Order,
+    Size,
+    Directed,
+    Source,
+    Target,
+}
+impl Segment<'_> {
+    /// Returns the placeholder with the given name
+    fn placeholder(name: &str) -> Option<Self> {
+        match name {
+            "n" => Some(Self::Order),
+            "m" => Some(Self::Size),
+            "directed" => Some(Self::Directed),
+            "u" => Some(Self::Source),
+            "v" => Some(Self::Target),
+            _ => None,
+        }
+    }
+}
+
+/// Values substituted for the placeholders
+struct Values {
+    n: usize,
+    m: usize,
+    directed: bool,
+    /// Unset outside edge parts
+    u: Option<usize>,
+    /// Unset outside vertex and edge parts
+    v: Option<usize>,
+}
+
+/// Writes a graph through a template
+pub(crate) fn write_template<G: GraphConversion + ?Sized>(
+    graph: &G,
+    out: &mut dyn fmt::Write,
+    template: &Template,
+) -> fmt::Result {
+    let edges = graph.edges();
+    let offset = template.one_based as usize;
+    let mut values = Values {
+        n: graph.size(),
+        m: edges.len(),
+        directed: graph.is_directed(),
+        u: None,
+        v: None,
+    };
+
+    write_segments(out, &parse(&template.header), &values)?;
+    let vertex = parse(&template.vertex);
+    for v in 0..values.n {
+        values.v = Some(v + offset);
+        write_segments(out, &vertex, &values)?;
+    }
+    let edge = parse(&template.edge);
+    for (u, v) in edges {
+        values.u = Some(u + offset);
+        values.v = Some(v + offset);
+        write_segments(out, &edge, &values)?;
+    }
+    values.u = None;
+    values.v = None;
+    write_segments(out, &parse(&template.footer), &values)
+}
+
+/// Splits a template part into text and placeholders
+fn parse(part: &str) -> Vec<Segment<'_>> {
+    let bytes = part.as_bytes();
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1)) {
+            // escaped braces keep the first of the pair
+            (b'{', Some(b'{')) | (b'}', Some(b'}')) => {
+                segments.push(Segment::Text(&part[start..=i]));
+                i += 2;
+                start = i;
+            }
+            (b'{', _) => {
+                let placeholder = part[i + 1..].find('}').and_then(|len| {
+                    Segment::placeholder(&part[i + 1..i + 1 + len]).map(|p| (p, len))
+                });
+                match placeholder {
+                    Some((placeholder, len)) => {
+                        segments.push(Segment::Text(&part[start..i]));
+                        segments.push(placeholder);
+                        i += len + 2;
+                        start = i;
+                    }
+                    None => i += 1,
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    segments.push(Segment::Text(&part[start..]));
+    segments.retain(|segment| *segment != Segment::Text(""));
+    segments
+}
+
+/// Writes a parsed template part
+fn write_segments(out: &mut dyn fmt::Write, segments: &[Segment], values: &Values) -> fmt::Result {
+    for segment in segments {
+        match segment {
+            Segment::Text(text) => out.write_str(text)?,
+            Segment::Order => write!(out, "{}", values.n)?,
+            Segment::Size => write!(out, "{}", values.m)?,
+            Segment::Directed => write!(out, "{}", values.directed)?,
+            Segment::Source => match values.u {
+                Some(u) => write!(out, "{}", u)?,
+                None => out.write_str("{u}")?,
+            },
+            Segment::Target => match values.v {
+                Some(v) => write!(out, "{}", v)?,
+                None => out.write_str("{v}")?,
+            },
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod testing {
+    use super::{parse, Segment, Template};
+    use crate::{Graph, GraphConversion};
+
+    #[test]
+    fn test_parse() {
+        let segments = parse("e {u}-{v} {{x}} {w} {");
+        assert_eq!(
+            segments,
+            vec![
+                Segment::Text("e "),
+                Segment::Source,
+                Segment::Text("-"),
+                Segment::Target,
+                Segment::Text(" {"),
+                Segment::Text("x}"),
+                Segment::Text(" {w} {"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_vertices_and_footer() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let template = Template::new()
+            .header("{{\"n\": {n}, \"directed\": {directed}}}\n")
+            .vertex("node {v}\n")
+            .edge("{u} -- {v}\n")
+            .footer("end {m}\n");
+        assert_eq!(
+            graph.to_template(&template),
+            "{\"n\": 2, \"directed\": false}\nnode 0\nnode 1\n0 -- 1\nend 1\n"
+        );
+    }
+
+    #[test]
+    fn test_vertex_placeholders_outside_edges() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let template = Template::new()
+            .header("{u} {v}\n")
+            .vertex("{u}:{v}\n")
+            .edge("{u}-{v}\n")
+            .footer("{u} {v} {n}\n");
+        assert_eq!(
+            graph.to_template(&template),
+            "{u} {v}\n{u}:0\n{u}:1\n0-1\n{u} {v} 2\n"
+        );
+    }
+
+    #[test]
+    fn test_empty_template() {
+        let graph = Graph::from_g6("Bw").unwrap();
+        assert_eq!(graph.to_template(&Template::new()), "");
+    }
+}
@@ -69,7 +69,7 @@ impl Graph {
 
     /// Builds the bitvector from the graph6 representation
     fn build_bitvector(bytes: &[u8], n: usize) -> Result<Vec<usize>, IOError> {
-        let bv_len = n * (n - 1) / 2;
+        let bv_len = n * n.saturating_sub(1) / 2;
         let Some(bit_vec) = fill_bitvector(bytes, bv_len, 1) else {
             return Err(IOError::NonCanonicalEncoding);
         };
@@ -115,6 +115,10 @@ impl WriteGraph for Graph {}
 #[cfg(test)]
 mod testing {
     use super::{Graph, GraphConversion, WriteGraph};
+    use crate::{
+        DimacsFormat, DotOptions, EdgeListOptions, ListgFormat, NetOptions, NodeAttribute,
+        SvgOptions, TikzOptions,
+    };
 
     #[test]
     fn test_graph_n2() {
@@ -185,7 +189,40 @@ mod testing {
         let repr = r"A_";
         let graph = Graph::from_g6(repr).unwrap();
         let net = graph.to_net();
-        assert_eq!(net, "*Vertices 2\n1 \"0\"\n2 \"1\"\n*Arcs\n1 2\n2 1\n");
+        assert_eq!(net, "*Vertices 2\n1 \"0\"\n2 \"1\"\n*Edges\n1 2\n");
+    }
+
+    #[test]
+    fn test_to_net_compact() {
+        let graph = Graph::from_g6("Bo").unwrap();
+        let net = graph.to_net_with(&NetOptions::new().compact(true));
+        assert_eq!(
+            net,
+            "*Vertices 3\n1 \"0\"\n2 \"1\"\n3 \"2\"\n*Edgeslist\n1 2 3\n"
+        );
+    }
+
+    #[test]
+    fn test_to_net_labels_positions() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let options = NetOptions::new()
+            .labels(["left"])
+            .positions(&[(-1.0, 1.0), (1.0, -1.0)]);
+        let net = graph.to_net_with(&options);
+        assert_eq!(
+            net,
+            "*Vertices 2\n1 \"left\" 0.0000 0.0000\n2 \"1\" 1.0000 1.0000\n*Edges\n1 2\n"
+        );
+    }
+
+    #[test]
+    fn test_to_net_quoted_labels() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let net = graph.to_net_with(&NetOptions::new().labels(["say \"hi\"", r"C:\"]));
+        assert_eq!(
+            net,
+            "*Vertices 2\n1 \"say \\\"hi\\\"\"\n2 \"C:\\\\\"\n*Edges\n1 2\n"
+        );
     }
 
     #[test]
@@ -235,4 +272,414 @@ mod testing {
         let graph = Graph::from_adj(adj);
         assert!(graph.is_err());
     }
+
+    #[test]
+    fn test_edges() {
+        let graph = Graph::from_g6("Bw").unwrap();
+        assert_eq!(graph.edges(), vec![(0, 1), (0, 2), (1, 2)]);
+    }
+
+    #[test]
+    fn test_to_graphml() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let graphml = graph.to_graphml(None);
+        assert!(graphml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<graphml "));
+        assert!(graphml.ends_with(
+            "  <graph edgedefault=\"undirected\">\n    <node id=\"n0\"/>\n    <node id=\"n1\"/>\n    <edge source=\"n0\" target=\"n1\"/>\n  </graph>\n</graphml>\n"
+        ));
+    }
+
+    #[test]
+    fn test_to_graphml_with_id() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let graphml = graph.to_graphml(Some(1));
+        assert!(graphml.contains("<graph id=\"graph_1\" edgedefault=\"undirected\">"));
+    }
+
+    #[test]
+    fn test_to_graphml_document() {
+        let graphs = vec![Graph::from_g6("A_").unwrap(), Graph::from_g6("A?").unwrap()];
+        let graphml = crate::to_graphml_document(&graphs);
+        assert_eq!(graphml.matches("<graph ").count(), 2);
+        assert!(graphml.contains("<graph id=\"graph_0\" edgedefault=\"undirected\">"));
+        assert!(graphml.contains("<node id=\"g1n1\"/>"));
+        assert_eq!(graphml.matches("<edge ").count(), 1);
+    }
+
+    #[test]
+    fn test_to_gexf() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let gexf = graph.to_gexf(&[]);
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gexf xmlns="http://gexf.net/1.3" version="1.3">
+  <graph defaultedgetype="undirected" mode="static">
+    <nodes>
+      <node id="0" label="0"/>
+      <node id="1" label="1"/>
+    </nodes>
+    <edges>
+      <edge id="0" source="0" target="1"/>
+    </edges>
+  </graph>
+</gexf>
+"#;
+        assert_eq!(gexf, expected);
+    }
+
+    #[test]
+    fn test_to_gexf_degree() {
+        let graph = Graph::from_g6("Bo").unwrap();
+        let gexf = graph.to_gexf(&[NodeAttribute::Degree]);
+        assert!(gexf.contains("<attribute id=\"0\" title=\"degree\" type=\"integer\"/>"));
+        assert_eq!(gexf.matches("<attvalue for=\"0\" value=\"1\"/>").count(), 2);
+        assert_eq!(gexf.matches("<attvalue for=\"0\" value=\"2\"/>").count(), 1);
+    }
+
+    #[test]
+    fn test_to_gml() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let gml = graph.to_gml();
+        let expected = r#"graph [
+  directed 0
+  node [
+    id 0
+    label "0"
+  ]
+  node [
+    id 1
+    label "1"
+  ]
+  edge [
+    source 0
+    target 1
+  ]
+]
+"#;
+        assert_eq!(gml, expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_json() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let json = graph.to_json(crate::JsonLayout::NodeLink);
+        assert_eq!(
+            json,
+            r#"{"directed":false,"multigraph":false,"graph":{},"nodes":[{"id":0},{"id":1}],"links":[{"source":0,"target":1}]}"#
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_json_cytoscape() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let json = graph.to_json(crate::JsonLayout::Cytoscape);
+        assert_eq!(
+            json,
+            r#"{"directed":false,"elements":{"nodes":[{"data":{"id":"0"}},{"data":{"id":"1"}}],"edges":[{"data":{"id":"e0-1","source":"0","target":"1"}}]}}"#
+        );
+    }
+
+    #[test]
+    fn test_to_edge_list() {
+        let graph = Graph::from_g6("Bw").unwrap();
+        let list = graph.to_edge_list(&EdgeListOptions::new());
+        assert_eq!(list, "0 1\n0 2\n1 2\n");
+    }
+
+    #[test]
+    fn test_to_edge_list_options() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let options = EdgeListOptions::new()
+            .separator("\t")
+            .one_based(true)
+            .header(true);
+        assert_eq!(graph.to_edge_list(&options), "2\t1\n1\t2\n");
+    }
+
+    #[test]
+    fn test_to_adjacency_list() {
+        let graph = Graph::from_g6("Bo").unwrap();
+        let adj = graph.to_adjacency_list();
+        assert_eq!(adj, "0: 1 2\n1: 0\n2: 0\n");
+    }
+
+    #[test]
+    fn test_to_dimacs() {
+        let graph = Graph::from_g6("Bw").unwrap();
+        let dimacs = graph.to_dimacs(DimacsFormat::Edge).unwrap();
+        assert_eq!(dimacs, "p edge 3 3\ne 1 2\ne 1 3\ne 2 3\n");
+    }
+
+    #[test]
+    fn test_to_dimacs_col() {
+        let graph = Graph::from_g6("A?").unwrap();
+        let dimacs = graph.to_dimacs(DimacsFormat::Col).unwrap();
+        assert_eq!(dimacs, "p col 2 0\n");
+    }
+
+    #[test]
+    fn test_to_metis() {
+        let graph = Graph::from_g6("Bo").unwrap();
+        let metis = graph.to_metis().unwrap();
+        assert_eq!(metis, "3 2\n2 3\n1\n1\n");
+    }
+
+    #[test]
+    fn test_to_matrix_market() {
+        let graph = Graph::from_g6("Bo").unwrap();
+        let mm = graph.to_matrix_market();
+        assert_eq!(
+            mm,
+            "%%MatrixMarket matrix coordinate pattern symmetric\n3 3 2\n2 1\n3 1\n"
+        );
+    }
+
+    #[test]
+    fn test_to_tikz() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let tikz = graph.to_tikz(&TikzOptions::new());
+        let expected = r"\begin{tikzpicture}
+  \node[circle, draw, inner sep=2pt] (v0) at (2.000, 0.000) {0};
+  \node[circle, draw, inner sep=2pt] (v1) at (-2.000, 0.000) {1};
+  \draw (v0) -- (v1);
+\end{tikzpicture}
+";
+        assert_eq!(tikz, expected);
+    }
+
+    #[test]
+    fn test_to_tikz_styles() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let options = TikzOptions::new()
+            .scale(1.0)
+            .node_style("fill")
+            .edge_style("thick")
+            .labels(false);
+        let tikz = graph.to_tikz(&options);
+        assert!(tikz.contains("\\node[fill] (v1) at (-1.000, 0.000) {};"));
+        assert!(tikz.contains("\\draw[thick] (v0) -- (v1);"));
+    }
+
+    #[test]
+    fn test_to_mermaid() {
+        let graph = Graph::from_g6("B_").unwrap();
+        let mermaid = graph.to_mermaid();
+        assert_eq!(mermaid, "graph LR\n    2\n    0 --- 1\n");
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let svg = graph.to_svg(&SvgOptions::new().size(100.0));
+        let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100" viewBox="0 0 100 100">
+<line x1="80.0" y1="50.0" x2="20.0" y2="50.0" stroke="black"/>
+<circle cx="80.0" cy="50.0" r="10.0" fill="white" stroke="black"/>
+<text x="80.0" y="50.0" font-size="10.0" text-anchor="middle" dominant-baseline="central">0</text>
+<circle cx="20.0" cy="50.0" r="10.0" fill="white" stroke="black"/>
+<text x="20.0" y="50.0" font-size="10.0" text-anchor="middle" dominant-baseline="central">1</text>
+</svg>
+"#;
+        assert_eq!(svg, expected);
+    }
+
+    #[test]
+    fn test_to_dot_positioned() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let dot = graph.to_dot_positioned(None, &[(1.0, 0.0), (-1.0, 0.5)]);
+        assert_eq!(
+            dot,
+            "graph {\n0 [pos=\"2.000,0.000!\"];\n1 [pos=\"-2.000,1.000!\"];\n0 -- 1;\n}"
+        );
+        let options = DotOptions::new().positions(&[(1.0, 0.0)]).scale(0.5);
+        let dot = graph.to_dot_with(&options);
+        assert!(dot.starts_with("graph {\n0 [pos=\"0.500,0.000!\"];\n0 -- 1;"));
+    }
+
+    #[test]
+    fn test_to_net_positioned() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let positions = crate::Layout::Circular.positions(&graph);
+        let net = graph.to_net_positioned(&positions);
+        assert!(net.starts_with("*Vertices 2\n1 \"0\" 1.0000 0.5000\n2 \"1\" 0.0000 0.5000\n"));
+    }
+
+    #[test]
+    fn test_to_npy() {
+        let graph = Graph::from_g6("A_").unwrap();
+        let npy = graph.to_npy(crate::NpyDtype::Bool);
+        assert_eq!(npy.len(), 128 + 4);
+        assert!(npy.starts_with(
+            b"\x93NUMPY\x01\x00\x76\x00{'descr': '|b1', 'fortran_order': False, 'shape': (2, 2), }"
+        ));
+        assert_eq!(&npy[128..], &[0, 1, 1, 0]);
+    }
+
+    #[test]
+    fn test_to_mathematica() {
+        let graph = Graph::from_g6("B_").unwrap();
+        let expr = graph.to_mathematica();
+        assert_eq!(expr, "Graph[{1, 2, 3}, {1 <-> 2}]");
+    }
+
+    #[test]
+    fn test_to_sage() {
+        let graph = Graph::from_g6("Bw").unwrap();
+        let expr = graph.to_sage();
+        assert_eq!(expr, "Graph({0: [1, 2], 1: [2], 2: []})");
+    }
+
+    #[test]
+    fn test_to_maple() {
+        let graph = Graph::from_g6("Bo").unwrap();
+        let expr = graph.to_maple();
+        assert_eq!(expr, "GraphTheory:-Graph(3, {{1, 2}, {1, 3}})");
+    }
+
+    #[test]
+    fn test_to_listg() {
+        let graph = Graph::from_g6("Bo").unwrap();
+        let listg = graph.to_listg(ListgFormat::List, 1);
+        assert_eq!(
+            listg,
+            "\nGraph 1, order 3.\n  0 : 1 2;\n  1 : 0;\n  2 : 0;\n"
+        );
+        let listg = graph.to_listg(ListgFormat::Matrix, 1);
+        assert_eq!(listg, "\nGraph 1, order 3.\n011\n100\n100\n");
+        let listg = graph.to_listg(ListgFormat::Edges, 2);
+        assert_eq!(listg, "\nGraph 2, order 3.\n3 2\n0 1  0 2\n");
+    }
+
+    #[test]
+    fn test_to_listg_wrapping() {
+        let graph = complete(62);
+        let listg = graph.to_listg(ListgFormat::Edges, 1);
+        let lines: Vec<&str> = listg.lines().skip(2).collect();
+        assert_eq!(lines[0], "62 1891");
+        assert!(lines[1].starts_with("0 1  0 2  0 3"));
+        // a line is only broken once more than 68 columns are used
+        let (last, full) = lines[1..].split_last().unwrap();
+        assert!(full.iter().all(|line| line.len() > 68 && line.len() <= 78));
+        assert!(!last.is_empty() && last.ends_with("60 61"));
+        assert!(listg.ends_with("60 61\n"));
+
+        let adj: Vec<usize> = (0..40 * 40).map(|i| (i / 40 != i % 40) as usize).collect();
+        let graph = Graph::from_adj(&adj).unwrap();
+        let listg = graph.to_listg(ListgFormat::List, 1);
+        let lines: Vec<&str> = listg.lines().skip(2).collect();
+        assert_eq!(lines.len(), 80);
+        assert!(lines[0].starts_with("  0 : 1 2 3"));
+        assert!(lines[0].ends_with(" 27"));
+        assert_eq!(lines[0].len(), 77);
+        assert_eq!(lines[1], "    28 29 30 31 32 33 34 35 36 37 38 39;");
+        assert!(lines.iter().all(|line| line.len() < 78));
+    }
+
+    #[test]
+    fn test_to_listg_edgeless() {
+        let graph = Graph::from_g6("B?").unwrap();
+        let header = "\nGraph 1, order 3.\n";
+        let listg = graph.to_listg(ListgFormat::List, 1);
+        assert_eq!(listg, format!("{header}  0 :;\n  1 :;\n  2 :;\n"));
+        let listg = graph.to_listg(ListgFormat::Matrix, 1);
+        assert_eq!(listg, format!("{header}000\n000\n000\n"));
+        let listg = graph.to_listg(ListgFormat::Edges, 1);
+        assert_eq!(listg, format!("{header}3 0\n\n"));
+        assert_eq!(graph.to_dreadnaut(), "n=3 $=0 g\n;\n;\n.\n");
+    }
+
+    #[test]
+    fn test_to_dreadnaut() {
+        let graph = Graph::from_g6("Bw").unwrap();
+        assert_eq!(graph.to_dreadnaut(), "n=3 $=0 g\n 1 2;\n 0 2;\n 0 1.\n");
+    }
+
+    #[test]
+    fn test_to_dreadnaut_wrapping() {
+        let dreadnaut = complete(62).to_dreadnaut();
+        let lines: Vec<&str> = dreadnaut.lines().collect();
+        assert_eq!(lines[0], "n=62 $=0 g");
+        assert!(lines.iter().all(|line| line.len() < 78));
+        assert!(lines[1].ends_with(" 28"));
+        assert_eq!(
+            lines[2],
+            "    29 30 31 32 33 34 35 36 37 38 39 40 41 42 43 44 45 46 47 48 49 50 51 52"
+        );
+        assert!(lines[3].starts_with("    53 ") && lines[3].ends_with(" 61;"));
+        assert!(dreadnaut.ends_with(" 60.\n"));
+    }
+
+    /// Compares the nauty layouts against nauty's own `listg`
+    ///
+    /// Run with `cargo test -- --ignored` on a machine with nauty installed.
+    #[test]
+    #[ignore = "requires nauty's listg on PATH"]
+    fn test_listg_matches_nauty() {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let graphs = [
+            Graph::from_g6("D??").unwrap(),
+            Graph::from_g6("Bw").unwrap(),
+            complete(62),
+        ];
+        for graph in &graphs {
+            let input = format!("{}\n", graph.write_graph());
+            let cases = [
+                ("", graph.to_listg(ListgFormat::List, 1)),
+                ("-a", graph.to_listg(ListgFormat::Matrix, 1)),
+                ("-e", graph.to_listg(ListgFormat::Edges, 1)),
+                ("-d", graph.to_dreadnaut()),
+            ];
+            for (flag, expected) in cases {
+                let mut listg = Command::new("listg")
+                    .args(Some(flag).filter(|flag| !flag.is_empty()))
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::null())
+                    .spawn()
+                    .expect("listg is not installed");
+                listg
+                    .stdin
+                    .take()
+                    .unwrap()
+                    .write_all(input.as_bytes())
+                    .unwrap();
+                let output = listg.wait_with_output().unwrap();
+                let output = String::from_utf8(output.stdout).unwrap();
+                assert_eq!(output, expected, "listg {} on {}", flag, input.trim());
+            }
+        }
+    }
+
+    /// Returns the complete graph on `n` vertices
+    fn complete(n: usize) -> Graph {
+        let adj: Vec<usize> = (0..n * n).map(|i| (i / n != i % n) as usize).collect();
+        Graph::from_adj(&adj).unwrap()
+    }
+
+    #[test]
+    fn test_write_matches_to() {
+        let graph = Graph::from_g6("C~").unwrap();
+        let mut out = String::new();
+        graph.write_adjmat(&mut out).unwrap();
+        assert_eq!(out, graph.to_adjmat());
+        out.clear();
+        graph.write_net(&mut out).unwrap();
+        assert_eq!(out, graph.to_net());
+        out.clear();
+        graph.write_dot(&mut out, Some(1)).unwrap();
+        assert_eq!(out, graph.to_dot(Some(1)));
+        out.clear();
+        graph.write_sage(&mut out).unwrap();
+        assert_eq!(out, graph.to_sage());
+    }
+
+    #[test]
+    fn test_write_npy() {
+        let graph = Graph::from_g6("Bw").unwrap();
+        let mut npy = Vec::new();
+        graph.write_npy(&mut npy, crate::NpyDtype::F32).unwrap();
+        assert_eq!(npy, graph.to_npy(crate::NpyDtype::F32));
+    }
 }
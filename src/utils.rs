@@ -6,6 +6,12 @@ pub fn fill_bitvector(bytes: &[u8], size: usize, offset: usize) -> Option<Vec<us
     let mut bit_vec = Vec::with_capacity(size);
     let mut pos = 0;
     for b in bytes.iter().skip(offset) {
+        if pos == size {
+            break;
+        }
+        if *b > 126 {
+            return None;
+        }
         let b = b.checked_sub(63)?;
         for i in 0..6 {
             let bit = (b >> (5 - i)) & 1;
@@ -21,7 +27,9 @@ pub fn fill_bitvector(bytes: &[u8], size: usize, offset: usize) -> Option<Vec<us
 
 /// Returns the size of the graph
 pub fn get_size(bytes: &[u8], pos: usize) -> Result<usize, IOError> {
-    let size = bytes[pos];
+    let Some(&size) = bytes.get(pos) else {
+        return Err(IOError::InvalidSizeChar);
+    };
     if size == 126 {
         Err(IOError::GraphTooLarge)
     } else if size < 63 {
@@ -33,7 +41,7 @@ pub fn get_size(bytes: &[u8], pos: usize) -> Result<usize, IOError> {
 
 /// Returns the upper triangle of a bitvector
 pub fn upper_triangle(bit_vec: &[usize], n: usize) -> Vec<usize> {
-    let mut tri = Vec::with_capacity(n * (n - 1) / 2);
+    let mut tri = Vec::with_capacity(n * n.saturating_sub(1) / 2);
     for i in 1..n {
         for j in 0..i {
             let idx = i * n + j;
@@ -43,9 +51,34 @@ pub fn upper_triangle(bit_vec: &[usize], n: usize) -> Vec<usize> {
     tri
 }
 
+/// Small seedable pseudo-random number generator (SplitMix64)
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next 64 random bits
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a uniform random integer in `0..bound`
+    pub fn next_below(&mut self, bound: u64) -> u64 {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+}
+
 #[cfg(test)]
 mod testing {
-    use super::get_size;
+    use super::{get_size, SplitMix64};
 
     #[test]
     fn test_size_pos_0() {
@@ -75,6 +108,17 @@ mod testing {
         assert_eq!(size, super::IOError::InvalidSizeChar);
     }
 
+    #[test]
+    fn test_size_missing() {
+        let size = get_size(b"&", 1).unwrap_err();
+        assert_eq!(size, super::IOError::InvalidSizeChar);
+    }
+
+    #[test]
+    fn test_bitvector_invalid_char() {
+        assert_eq!(super::fill_bitvector(b"B\xff", 3, 1), None);
+    }
+
     #[test]
     fn test_bitvector() {
         let bytes = b"Bw";
@@ -104,4 +148,22 @@ mod testing {
         let tri = super::upper_triangle(&bit_vec, 3);
         assert_eq!(tri, vec![1, 1, 0]);
     }
+
+    #[test]
+    fn test_splitmix_seeded() {
+        let a: Vec<u64> = (0..4)
+            .map({
+                let mut rng = SplitMix64::new(7);
+                move |_| rng.next_u64()
+            })
+            .collect();
+        let mut rng = SplitMix64::new(7);
+        assert!(a.iter().all(|&x| x == rng.next_u64()));
+    }
+
+    #[test]
+    fn test_splitmix_below() {
+        let mut rng = SplitMix64::new(0);
+        assert!((0..1000).all(|_| rng.next_below(10) < 10));
+    }
 }
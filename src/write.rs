@@ -1,4 +1,13 @@
-use crate::{utils::upper_triangle, GraphConversion};
+use crate::{
+    compress::{compress, Compression, Encoder},
+    utils::upper_triangle,
+    GraphConversion,
+};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
 
 /// Trait to write graphs into graph 6 formatted strings
 pub trait WriteGraph: GraphConversion {
@@ -7,6 +16,71 @@ pub trait WriteGraph: GraphConversion {
     }
 }
 
+/// Writes graphs as graph6/digraph6 formatted lines into a writer
+///
+/// # Example
+/// ```
+/// use graph6_rs::{Graph, GraphWriter};
+/// let mut writer = GraphWriter::new(Vec::new());
+/// writer.write_graph(&Graph::from_g6("A_").unwrap()).unwrap();
+/// writer.write_line("Bw").unwrap();
+/// assert_eq!(writer.into_inner(), b"A_\nBw\n");
+/// ```
+#[derive(Debug)]
+pub struct GraphWriter<W> {
+    inner: W,
+}
+impl<W: Write> GraphWriter<W> {
+    /// Creates a new writer over an output
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Encodes a graph and writes it as a single line
+    pub fn write_graph<G: WriteGraph + ?Sized>(&mut self, graph: &G) -> io::Result<()> {
+        self.write_line(&graph.write_graph())
+    }
+
+    /// Writes an already encoded graph as a single line
+    pub fn write_line(&mut self, repr: &str) -> io::Result<()> {
+        self.inner.write_all(repr.as_bytes())?;
+        self.inner.write_all(b"\n")
+    }
+
+    /// Flushes the underlying output
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    /// Consumes the writer and returns the underlying output
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+impl GraphWriter<Encoder<BufWriter<File>>> {
+    /// Creates a graph file for writing
+    ///
+    /// Files ending in `.gz` or `.zst` are compressed with gzip or zstd
+    /// (requires the `gzip`/`zstd` features). Call
+    /// [`GraphWriter::finish`] once all graphs are written to complete the
+    /// file and check for errors.
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let compression = Compression::from_path(&path);
+        if !compression.is_supported() {
+            // report the missing feature before `File::create` truncates the file
+            compress(io::sink(), compression)?;
+        }
+        let file = BufWriter::new(File::create(path)?);
+        Ok(Self::new(compress(file, compression)?))
+    }
+}
+impl<W: Write> GraphWriter<Encoder<W>> {
+    /// Completes the compressed stream, flushes and returns the output
+    pub fn finish(self) -> io::Result<W> {
+        self.inner.finish()
+    }
+}
+
 fn write_header(repr: &mut String, is_directed: bool) {
     if is_directed {
         repr.push('&');
@@ -19,7 +93,7 @@ fn write_size(repr: &mut String, size: usize) {
 }
 
 fn pad_bitvector(bit_vec: &mut Vec<usize>) {
-    if bit_vec.len() % 6 != 0 {
+    if !bit_vec.len().is_multiple_of(6) {
         (0..6 - (bit_vec.len() % 6)).for_each(|_| bit_vec.push(0));
     }
 }
@@ -51,6 +125,57 @@ pub fn write_graph6(bit_vec: Vec<usize>, n: usize, is_directed: bool) -> String
 
 #[cfg(test)]
 mod testing {
+    use super::GraphWriter;
+    use crate::{Graph, GraphReader};
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("graph6-rs-{}-{}", std::process::id(), name))
+    }
+
+    fn roundtrip_file(name: &str) {
+        let path = temp_path(name);
+        let mut writer = GraphWriter::create(&path).unwrap();
+        writer.write_graph(&Graph::from_g6("A_").unwrap()).unwrap();
+        writer.write_line("Bw").unwrap();
+        writer.finish().unwrap();
+        let graphs: Vec<Graph> = GraphReader::open(&path)
+            .unwrap()
+            .graphs()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(graphs.len(), 2);
+        assert_eq!(graphs[1].n, 3);
+    }
+
+    #[test]
+    fn test_graph_file() {
+        roundtrip_file("roundtrip.g6");
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_graph_file_gzip() {
+        roundtrip_file("roundtrip.g6.gz");
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_graph_file_zstd() {
+        roundtrip_file("roundtrip.g6.zst");
+    }
+
+    #[cfg(not(feature = "gzip"))]
+    #[test]
+    fn test_unsupported_keeps_existing_file() {
+        let path = temp_path("precious.g6.gz");
+        std::fs::write(&path, "precious").unwrap();
+        let err = GraphWriter::create(&path).unwrap_err();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+        assert_eq!(contents, "precious");
+    }
 
     #[test]
     fn test_write_undirected_n2() {